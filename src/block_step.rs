@@ -1,20 +1,23 @@
 use crate::epoch::epoch;
 use crate::root::{get_root_netuid, root_epoch};
+use crate::registration::{prune_used_work, record_block_seed};
+use crate::slashing::slash_low_trust_validators;
 use crate::staking::{
-    hotkey_is_delegate, increase_stake_on_coldkey_hotkey_account, increase_stake_on_hotkey_account,
+    credit_rewards_on_hotkey_account, distribute_rewards_to_nominators, expire_stake_locks,
+    hotkey_is_delegate,
 };
 use crate::state::{
     ADJUSTMENTS_ALPHA, ADJUSTMENT_INTERVAL, BLOCKS_SINCE_LAST_STEP, BURN,
     BURN_REGISTRATIONS_THIS_INTERVAL, DELEGATES, DIFFICULTY, EMISSION_VALUES,
     LAST_ADJUSTMENT_BLOCK, LAST_MECHANISM_STEP_BLOCK, LOADED_EMISSION, MAX_BURN, MAX_DIFFICULTY,
     MIN_BURN, MIN_DIFFICULTY, NETWORKS_ADDED, PENDING_EMISSION, POW_REGISTRATIONS_THIS_INTERVAL,
-    REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, STAKE, SUBNET_OWNER,
-    TARGET_REGISTRATIONS_PER_INTERVAL, TEMPO, TOTAL_HOTKEY_STAKE,
+    REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, SUBNET_OWNER,
+    TARGET_REGISTRATIONS_PER_INTERVAL, TEMPO,
     TOTAL_ISSUANCE,
 };
 use crate::utils::{ensure_root, get_blocks_since_last_step};
 use crate::ContractError;
-use cosmwasm_std::{Addr, Api, CosmosMsg, DepsMut, Env, Order, StdResult, Storage};
+use cosmwasm_std::{Addr, Api, BankMsg, DepsMut, Env, Order, StdResult, Storage};
 use cyber_std::Response;
 use substrate_fixed::types::I110F18;
 use substrate_fixed::types::I64F64;
@@ -34,6 +37,12 @@ pub fn block_step(deps: DepsMut, env: Env, caller: Option<Addr>) -> Result<Respo
         .debug(&format!("🕛 block_step for block: {:?} ", block_number));
     // --- 1. Adjust difficulties.
     adjust_registration_terms_for_networks(deps.storage, deps.api, env.block.height)?;
+    // --- 1a. Release expired stake locks.
+    expire_stake_locks(deps.storage, block_number)?;
+    // --- 1b. Record the seed of the block for PoW registrations.
+    record_block_seed(deps.storage, &env)?;
+    // --- 1c. Remove used PoW work which is out of the work window.
+    prune_used_work(deps.storage, block_number)?;
     // --- 2. Calculate per-subnet emissions
     match root_epoch(deps.storage, deps.api, block_number) {
         Ok(_) => {
@@ -48,12 +57,10 @@ pub fn block_step(deps: DepsMut, env: Env, caller: Option<Addr>) -> Result<Respo
     // --- 3. Drains emission tuples ( hotkey, amount ).
     drain_emission(deps.storage, deps.api, block_number)?;
     // --- 4. Generates emission tuples from epoch functions.
-    let _msgs = generate_emission(deps.storage, deps.api, block_number)?;
-    // Ok(Response::new()
-    //     .add_messages(msgs)
-    //     .add_attribute("action", "block_step")
-    // )
-    Ok(Response::default().add_attribute("action", "block_step"))
+    let msgs = generate_emission(deps.storage, deps.api, block_number)?;
+    Ok(Response::default()
+        .add_messages(msgs)
+        .add_attribute("action", "block_step"))
 }
 
 // Helper function which returns the number of blocks remaining before we will run the epoch on this
@@ -120,9 +127,9 @@ pub fn generate_emission(
     store: &mut dyn Storage,
     api: &dyn Api,
     block_number: u64,
-) -> Result<Vec<CosmosMsg>, ContractError> {
+) -> Result<Vec<BankMsg>, ContractError> {
     // --- 1. Iterate across each network and add pending emission into stash.
-    let msgs: Vec<CosmosMsg> = Vec::new();
+    let mut msgs: Vec<BankMsg> = Vec::new();
     let netuid_tempo: Vec<(u16, u16)> = TEMPO
         .range(store, None, None, Order::Ascending)
         .map(|item| {
@@ -201,6 +208,9 @@ pub fn generate_emission(
             netuid, emission_to_drain
         ));
 
+        // --- 8a. Slash validators which stayed out of consensus, if the subnet opted in.
+        msgs.append(&mut slash_low_trust_validators(store, api, netuid)?);
+
         // --- 9. Check that the emission does not exceed the allowed total.
         let emission_sum: u128 = emission_tuples_this_block
             .iter()
@@ -240,7 +250,7 @@ pub fn get_loaded_emission_tuples(store: &dyn Storage, netuid: u16) -> Vec<(Addr
 // Reads from the loaded emission storage which contains lists of pending emission tuples ( hotkey, amount )
 // and distributes small chunks of them at a time.
 //
-pub fn drain_emission(store: &mut dyn Storage, api: &dyn Api, _: u64) -> Result<(), ContractError> {
+pub fn drain_emission(store: &mut dyn Storage, api: &dyn Api, block_number: u64) -> Result<(), ContractError> {
     // --- 1. We iterate across each network.
     let netuid_tempo: Vec<(u16, u16)> = TEMPO
         .range(store, None, None, Order::Ascending)
@@ -263,6 +273,7 @@ pub fn drain_emission(store: &mut dyn Storage, api: &dyn Api, _: u64) -> Result<
                 &hotkey,
                 *server_amount,
                 *validator_amount,
+                block_number,
             )?;
             total_emitted += *server_amount + *validator_amount;
         }
@@ -284,59 +295,38 @@ pub fn emit_inflation_through_hotkey_account(
     hotkey: &Addr,
     server_emission: u64,
     validator_emission: u64,
+    block_number: u64,
 ) -> Result<(), ContractError> {
     // --- 1. Check if the hotkey is a delegate. If not, we simply pass the stake through to the
     // coldkey - hotkey account as normal.
     if !hotkey_is_delegate(store, &hotkey) {
-        increase_stake_on_hotkey_account(store, &hotkey, server_emission + validator_emission);
+        credit_rewards_on_hotkey_account(store, &hotkey, server_emission + validator_emission);
         return Ok(());
     }
     // Then this is a delegate, we distribute validator_emission, then server_emission.
 
     // --- 2. The hotkey is a delegate. We first distribute a proportion of the validator_emission to the hotkey
     // directly as a function of its 'take'
-    let total_hotkey_stake: u64 = TOTAL_HOTKEY_STAKE.load(store, &hotkey)?;
     let delegate_take: u64 =
         calculate_delegate_proportional_take(store, &hotkey, validator_emission);
     let validator_emission_minus_take: u64 = validator_emission - delegate_take;
-    let mut remaining_validator_emission: u64 = validator_emission_minus_take;
 
     // 3. -- The remaining emission goes to the owners in proportion to the stake delegated.
+    // It is accumulated in the delegate reward index and settled lazily into the nominators stake.
+    let nominators_emission: u64 =
+        distribute_rewards_to_nominators(store, &hotkey, validator_emission_minus_take, block_number);
+    api.debug(&format!(
+        "🤗 hotkey: {:?} nominators emission: +{:?} ",
+        hotkey.clone(),
+        nominators_emission
+    ));
 
-    let stake: Vec<(Addr, u64)> = STAKE
-        .prefix(&hotkey)
-        .range(store, None, None, Order::Ascending)
-        .map(|item| {
-            let i = item.unwrap();
-            (i.0, i.1)
-        })
-        .collect::<Vec<(Addr, u64)>>();
-
-    for (owning_coldkey_i, stake_i) in stake {
-        // --- 4. The emission proportion is remaining_emission * ( stake / total_stake ).
-        let stake_proportion: u64 = calculate_stake_proportional_emission(
-            stake_i,
-            total_hotkey_stake,
-            validator_emission_minus_take,
-        );
-        increase_stake_on_coldkey_hotkey_account(
-            store,
-            &owning_coldkey_i,
-            &hotkey,
-            stake_proportion,
-        );
-        api.debug(&format!(
-            "🤗 owning_coldkey_i: {:?} hotkey: {:?} emission: +{:?} ",
-            owning_coldkey_i,
-            hotkey.clone(),
-            stake_proportion
-        ));
-        remaining_validator_emission -= stake_proportion;
-    }
+    // --- 4. Whatever was not distributed (delegate without stake) stays with the delegate.
+    let remaining_validator_emission: u64 = validator_emission_minus_take - nominators_emission;
 
     // --- 5. Last increase final account balance of delegate after 4, since 5 will change the stake proportion of
     // the delegate and effect calculation in 4.
-    increase_stake_on_hotkey_account(store, &hotkey, delegate_take + remaining_validator_emission);
+    credit_rewards_on_hotkey_account(store, &hotkey, delegate_take + remaining_validator_emission);
     api.debug(&format!(
         "🤗 delkey: {:?} delegate_take: +{:?} ",
         hotkey, delegate_take
@@ -344,80 +334,11 @@ pub fn emit_inflation_through_hotkey_account(
     // Also emit the server_emission to the hotkey
     // The server emission is distributed in-full to the delegate owner.
     // We do this after 4. for the same reason as above.
-    increase_stake_on_hotkey_account(store, &hotkey, server_emission);
+    credit_rewards_on_hotkey_account(store, &hotkey, server_emission);
 
     Ok(())
 }
 
-// Increases the stake on the cold - hot pairing by increment while also incrementing other counters.
-// This function should be called rather than set_stake under account.
-//
-// TODO revisit
-#[cfg(test)]
-pub fn block_step_increase_stake_on_coldkey_hotkey_account(
-    store: &mut dyn Storage,
-    coldkey: &Addr,
-    hotkey: &Addr,
-    increment: u64,
-) -> StdResult<()> {
-    TOTAL_COLDKEY_STAKE.update(store, coldkey, |s| -> StdResult<_> {
-        Ok(s.unwrap_or_default().saturating_add(increment))
-    })?;
-    TOTAL_HOTKEY_STAKE.update(store, hotkey, |s| -> StdResult<_> {
-        Ok(s.unwrap_or_default().saturating_add(increment))
-    })?;
-    STAKE.update(store, (hotkey, coldkey), |s| -> StdResult<_> {
-        Ok(s.unwrap_or_default().saturating_add(increment))
-    })?;
-    TOTAL_STAKE.update(store, |a| -> StdResult<_> {
-        Ok(a.saturating_add(increment))
-    })?;
-    TOTAL_ISSUANCE.update(store, |a| -> StdResult<_> {
-        Ok(a.saturating_add(increment))
-    })?;
-    Ok(())
-}
-
-// Decreases the stake on the cold - hot pairing by the decrement while decreasing other counters.
-//
-// TODO revisit
-#[cfg(test)]
-pub fn block_step_decrease_stake_on_coldkey_hotkey_account(
-    store: &mut dyn Storage,
-    coldkey: &Addr,
-    hotkey: &Addr,
-    decrement: u64,
-) -> StdResult<()> {
-    TOTAL_COLDKEY_STAKE.update(store, coldkey, |s| -> StdResult<_> {
-        Ok(s.unwrap().saturating_sub(decrement))
-    })?;
-    TOTAL_HOTKEY_STAKE.update(store, hotkey, |s| -> StdResult<_> {
-        Ok(s.unwrap().saturating_sub(decrement))
-    })?;
-    STAKE.update(store, (hotkey, coldkey), |s| -> StdResult<_> {
-        Ok(s.unwrap().saturating_sub(decrement))
-    })?;
-    TOTAL_STAKE.update(store, |a| -> StdResult<_> {
-        Ok(a.saturating_sub(decrement))
-    })?;
-    TOTAL_ISSUANCE.update(store, |a| -> StdResult<_> {
-        Ok(a.saturating_sub(decrement))
-    })?;
-
-    Ok(())
-}
-
-// Returns emission awarded to a hotkey as a function of its proportion of the total stake.
-//
-pub fn calculate_stake_proportional_emission(stake: u64, total_stake: u64, emission: u64) -> u64 {
-    if total_stake == 0 {
-        return 0;
-    };
-    let stake_proportion = I64F64::from_num(stake) / I64F64::from_num(total_stake);
-    let proportional_emission = I64F64::from_num(emission) * stake_proportion;
-    return proportional_emission.to_num::<u64>();
-}
-
 // Returns the delegated stake 'take' assigned to this key. (If exists, otherwise 0)
 //
 pub fn calculate_delegate_proportional_take(
@@ -1,6 +1,6 @@
 use std::ops::{Div, Mul, Sub};
 
-use cosmwasm_std::{Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, ensure, Env, MessageInfo, Order, StdResult, Storage, to_json_binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, ensure, Env, MessageInfo, Order, StdResult, Storage, to_json_binary, Uint128};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cw2::{ContractVersion, get_contract_version, set_contract_version};
@@ -9,37 +9,44 @@ use cyber_std::{create_forget_thought_msg, Response};
 use cyber_std::{create_creat_thought_msg, Load, Trigger};
 
 use crate::block_step::block_step;
-use crate::delegate_info::{get_delegate, get_delegated, get_delegates};
+use crate::delegate_info::{get_delegate, get_delegated, get_delegates, get_nominators};
 use crate::error::ContractError;
 use crate::msg::{EconomyData, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
-use crate::neuron_info::{get_neuron, get_neuron_lite, get_neurons, get_neurons_lite};
-use crate::registration::{do_burned_registration, do_registration, do_sudo_registration};
+use crate::neuron_info::{get_neuron, get_neuron_lite, get_neurons, get_neurons_lite, get_pruning_candidates};
+use crate::registration::{
+    do_burned_registration, do_deregister, do_faucet, do_registration, do_staked_registration,
+    do_sudo_registration,
+    get_block_hash_from_u64, hash_to_vec,
+};
 use crate::root::{do_root_register, get_network_lock_cost, user_add_network, user_remove_network};
 use crate::serving::{do_serve_axon, do_serve_prometheus};
-use crate::stake_info::{get_stake_info_for_coldkey, get_stake_info_for_coldkeys};
-use crate::staking::{do_add_stake, do_become_delegate, do_remove_stake, do_set_delegate_commission};
-use crate::state::{ACTIVE, ACTIVITY_CUTOFF, ADJUSTMENT_INTERVAL, ADJUSTMENTS_ALPHA, ALLOW_FAUCET, AxonInfo, AXONS, BLOCK_EMISSION, BLOCKS_SINCE_LAST_STEP, BONDS_MOVING_AVERAGE, BURN, BURN_REGISTRATIONS_THIS_INTERVAL, COMMISSION_CHANGE, CONSENSUS, DEFAULT_TAKE, DELEGATES, DENOM, DIFFICULTY, DIVIDENDS, EMISSION, EMISSION_VALUES, IMMUNITY_PERIOD, INCENTIVE, KAPPA, LAST_ADJUSTMENT_BLOCK, LAST_UPDATE, MAX_ALLOWED_UIDS, MAX_ALLOWED_VALIDATORS, MAX_BURN, MAX_DIFFICULTY, MAX_REGISTRATION_PER_BLOCK, MAX_WEIGHTS_LIMIT, Metadata, MIN_ALLOWED_WEIGHTS, MIN_BURN, MIN_DIFFICULTY, NETWORK_IMMUNITY_PERIOD, NETWORK_LAST_LOCK_COST, NETWORK_LAST_REGISTERED, NETWORK_LOCK_REDUCTION_INTERVAL, NETWORK_MIN_LOCK_COST, NETWORK_MODALITY, NETWORK_RATE_LIMIT, NETWORK_REGISTERED_AT, NETWORK_REGISTRATION_ALLOWED, NETWORKS_ADDED, NETWORKS_METADATA, OWNER, PENDING_EMISSION, POW_REGISTRATIONS_THIS_INTERVAL, PROMETHEUS, PrometheusInfo, PRUNING_SCORES, RANK, RAO_RECYCLED_FOR_REGISTRATION, REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, RHO, ROOT, SERVING_RATE_LIMIT, STAKE, SUBNET_LIMIT, SUBNET_LOCKED, SUBNET_OWNER, SUBNET_OWNER_CUT, SUBNETWORK_N, TARGET_REGISTRATIONS_PER_INTERVAL, TEMPO, TOTAL_COLDKEY_STAKE, TOTAL_HOTKEY_STAKE, TOTAL_ISSUANCE, TOTAL_NETWORKS, TOTAL_REWARDS, TOTAL_STAKE, TRUST, TX_RATE_LIMIT, UIDS, VALIDATOR_PERMIT, VALIDATOR_TRUST, VERSE_METADATA, WEIGHTS_SET_RATE_LIMIT, WEIGHTS_VERSION_KEY};
+use crate::stake_info::{get_rewards_history, get_stake_info_for_coldkey, get_stake_info_for_coldkeys};
+use crate::staking::{add_unissued_claimable_balance, do_add_stake, do_add_stake_for, do_add_stake_multi, do_approve_hotkey_owner, do_become_delegate, do_claim, do_lock_stake, do_set_delegate_limits, do_set_reward_preference, get_claimable_for_coldkey, do_remove_stake, do_remove_stake_multi, do_set_delegate_commission, do_set_delegate_identity, get_pending_rewards_for_coldkey_and_hotkey, get_stake_for_coldkey_and_hotkey, get_total_claimable};
+use crate::state::{ACTIVE, ACTIVITY_CUTOFF, ADJUSTMENT_INTERVAL, ADJUSTMENTS_ALPHA, ALLOW_FAUCET, AxonInfo, AXONS, BLOCK_EMISSION, BLOCKS_PER_DAY, BLOCKS_SINCE_LAST_STEP, BONDS_MOVING_AVERAGE, BURN, BURN_REGISTRATIONS_THIS_INTERVAL, COMMISSION_CHANGE, CONSENSUS, DEFAULT_TAKE, DELEGATES, DELEGATES_BY_STAKE, DENOM, DIFFICULTY, DIVIDENDS, EMISSION, HOTKEY_OWNER_APPROVALS, EMISSION_VALUES, IMMUNITY_PERIOD, INCENTIVE, KAPPA, LAST_ADJUSTMENT_BLOCK, LAST_UPDATE, MAX_ALLOWED_UIDS, MAX_ALLOWED_VALIDATORS, MAX_BURN, MAX_DIFFICULTY, MAX_LOCK_BONUS, MAX_LOCK_DURATION, MAX_REGISTRATION_PER_BLOCK, MAX_WEIGHTS_LIMIT, Metadata, MIN_ALLOWED_WEIGHTS, MIN_BURN, MIN_DIFFICULTY, NETWORK_IMMUNITY_PERIOD, NETWORK_LAST_LOCK_COST, NETWORK_LAST_REGISTERED, NETWORK_LOCK_REDUCTION_INTERVAL, NETWORK_MIN_LOCK_COST, NETWORK_MODALITY, NETWORK_RATE_LIMIT, NETWORK_REGISTERED_AT, NETWORK_REGISTRATION_ALLOWED, NETWORKS_ADDED, NETWORKS_METADATA, OWNER, PENDING_EMISSION, POW_REGISTRATIONS_THIS_INTERVAL, PROMETHEUS, PrometheusInfo, PRUNING_SCORES, RANK, RAO_RECYCLED_FOR_REGISTRATION, REGISTRATION_ALLOWLIST, REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, REWARD_PREFERENCE, RewardPreference, RHO, ROOT, SERVING_RATE_LIMIT, SLASHING, SlashingConfig, STAKE, STAKE_LOCKS, StakeLock, STAKED_HOTKEYS, SUBNET_LIMIT, SUBNET_LOCKED, SUBNET_OWNER, SUBNET_OWNER_CUT, SUBNETWORK_N, TARGET_REGISTRATIONS_PER_INTERVAL, TEMPO, TOTAL_CLAIMABLE, TOTAL_COLDKEY_STAKE, TOTAL_HOTKEY_STAKE, TOTAL_ISSUANCE, TOTAL_NETWORKS, TOTAL_REWARDS, TOTAL_STAKE, TRUST, TX_RATE_LIMIT, UIDS, VALIDATOR_PERMIT, VALIDATOR_TRUST, VERSE_METADATA, WEIGHTS_SET_RATE_LIMIT, WEIGHTS_VERSION_KEY};
 use crate::state_info::get_state_info;
 use crate::subnet_info::{get_subnet_hyperparams, get_subnet_info, get_subnets_info};
 use crate::uids::get_registered_networks_for_hotkey;
 use crate::utils::{
     do_sudo_set_activity_cutoff, do_sudo_set_adjustment_alpha, do_sudo_set_adjustment_interval,
-    do_sudo_set_block_emission, do_sudo_set_bonds_moving_average, do_sudo_set_commission_change, do_sudo_set_default_take, do_sudo_set_difficulty,
+    do_sudo_set_block_emission, do_sudo_set_blocks_per_day, do_sudo_set_bonds_moving_average, do_sudo_set_commission_change, do_sudo_set_default_take, do_sudo_set_difficulty,
     do_sudo_set_immunity_period, do_sudo_set_kappa, do_sudo_set_lock_reduction_interval, do_sudo_set_max_allowed_uids,
-    do_sudo_set_max_allowed_validators, do_sudo_set_max_burn, do_sudo_set_max_difficulty, do_sudo_set_max_registrations_per_block,
+    do_sudo_set_max_allowed_validators, do_sudo_set_max_burn, do_sudo_set_max_difficulty, do_sudo_set_max_lock_bonus, do_sudo_set_max_lock_duration, do_sudo_set_max_registrations_per_block,
     do_sudo_set_max_weight_limit, do_sudo_set_min_allowed_weights, do_sudo_set_min_burn, do_sudo_set_min_difficulty,
     do_sudo_set_network_immunity_period, do_sudo_set_network_min_lock_cost, do_sudo_set_network_rate_limit, do_sudo_set_network_registration_allowed,
-    do_sudo_set_rao_recycled, do_sudo_set_rho, do_sudo_set_root, do_sudo_set_serving_rate_limit, do_sudo_set_subnet_limit,
+    do_sudo_set_rao_recycled, do_sudo_add_to_registration_allowlist, do_sudo_remove_from_registration_allowlist, do_sudo_set_registration_allowlist_enabled, do_sudo_set_registration_fee_destination, do_sudo_set_registration_mode, do_sudo_set_registration_stake, do_sudo_set_allow_faucet, do_sudo_set_faucet_config, do_sudo_fund_faucet, get_faucet_config, get_faucet_reserve, get_root_auto_delegate, get_root_min_stake, do_sudo_set_rho, do_sudo_set_root, do_sudo_set_root_auto_delegate, do_sudo_set_root_min_stake, do_sudo_set_serving_allow_private_ips, do_sudo_set_serving_rate_limit, do_sudo_set_slashing, do_sudo_set_subnet_limit,
     do_sudo_set_subnet_metadata, do_sudo_set_subnet_owner, do_sudo_set_subnet_owner_cut, do_sudo_set_target_registrations_per_interval,
     do_sudo_set_tempo, do_sudo_set_total_issuance, do_sudo_set_tx_rate_limit, do_sudo_set_validator_permit_for_uid,
     do_sudo_set_validator_prune_len, do_sudo_set_verse_metadata, do_sudo_set_weights_set_rate_limit, do_sudo_set_weights_version_key, do_sudo_unstake_all,
-    ensure_root
+    ensure_root, get_blocks_per_day, get_registration_fee_destination
 };
 use crate::weights::{do_set_weights, get_network_weights, get_network_weights_sparse};
 
 const CONTRACT_NAME: &str = "cybernet";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Max number of items returned by a page of the registration allowlist query.
+const MAX_ALLOWLIST_LIMIT: u16 = 256;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -80,6 +87,10 @@ pub fn instantiate(
     NETWORK_MIN_LOCK_COST.save(deps.storage, &10_000_000_000)?;
     NETWORK_LOCK_REDUCTION_INTERVAL.save(deps.storage, &(7 * 14400))?;
     TOTAL_REWARDS.save(deps.storage, &0)?;
+    BLOCKS_PER_DAY.save(deps.storage, &14400)?;
+    TOTAL_CLAIMABLE.save(deps.storage, &0)?;
+    MAX_LOCK_DURATION.save(deps.storage, &(14400 * 365))?;
+    MAX_LOCK_BONUS.save(deps.storage, &u16::MAX)?;
 
     // -- Root network initialization --
     let root_netuid: u16 = 0;
@@ -261,16 +272,22 @@ pub fn deactivate(
 
     let contract_balance = deps.querier.query_balance(env.clone().contract.address, denom.clone()).unwrap();
     let total_stake = TOTAL_STAKE.load(deps.storage)?;
-
-    let return_rewards_msg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: root.to_string(),
-        amount: vec![
-            Coin {
-                denom,
-                amount: contract_balance.amount.sub(Uint128::from(total_stake)),
-            },
-        ],
-    });
+    let total_claimable = get_total_claimable(deps.storage);
+    let total_locked = SUBNET_LOCKED
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, locked)| locked as u128))
+        .sum::<StdResult<u128>>()?;
+    let faucet_reserve = get_faucet_reserve(deps.storage);
+    let reserved =
+        total_stake as u128 + total_claimable as u128 + total_locked + faucet_reserve as u128;
+
+    // Rewards not backing stake, claims, locks or the faucet reserve are credited to the root
+    // claimable balance, they were never issued so the total issuance is not changed.
+    let return_rewards = contract_balance
+        .amount
+        .checked_sub(Uint128::from(reserved))
+        .map_err(|_| ContractError::ContractBalanceBelowReserved {})?;
+    add_unissued_claimable_balance(deps.storage, &root, return_rewards.u128() as u64);
 
     let disable_dmn = create_forget_thought_msg(
         env.contract.address.to_string(),
@@ -278,7 +295,6 @@ pub fn deactivate(
     );
 
     let res = Response::new()
-        .add_message(return_rewards_msg)
         .add_message(disable_dmn)
         .add_attribute("action", "deactivate");
 
@@ -304,12 +320,40 @@ pub fn execute(
         } => do_set_weights(deps, env, info, netuid, dests, weights, version_key),
         ExecuteMsg::BecomeDelegate { hotkey } => do_become_delegate(deps, env, info, hotkey),
         ExecuteMsg::AddStake { hotkey } => do_add_stake(deps, env, info, hotkey),
+        ExecuteMsg::AddStakeFor {
+            hotkey,
+            beneficiary_coldkey,
+        } => do_add_stake_for(deps, env, info, hotkey, beneficiary_coldkey),
         ExecuteMsg::RemoveStake { hotkey, amount } => {
             do_remove_stake(deps, env, info, hotkey, amount)
         }
+        ExecuteMsg::AddStakeMulti { allocations } => {
+            do_add_stake_multi(deps, env, info, allocations)
+        }
+        ExecuteMsg::RemoveStakeMulti { allocations } => {
+            do_remove_stake_multi(deps, env, info, allocations)
+        }
         ExecuteMsg::SetDelegateCommission { hotkey, commission } => {
             do_set_delegate_commission(deps, env, info, hotkey, commission)
         }
+        ExecuteMsg::SetDelegateIdentity { hotkey, identity } => {
+            do_set_delegate_identity(deps, env, info, hotkey, identity)
+        }
+        ExecuteMsg::SetDelegateLimits { hotkey, limits } => {
+            do_set_delegate_limits(deps, env, info, hotkey, limits)
+        }
+        ExecuteMsg::SetRewardPreference { hotkey, preference } => {
+            do_set_reward_preference(deps, env, info, hotkey, preference)
+        }
+        ExecuteMsg::Claim {} => do_claim(deps, env, info),
+        ExecuteMsg::ApproveHotkeyOwner { coldkey } => {
+            do_approve_hotkey_owner(deps, env, info, coldkey)
+        }
+        ExecuteMsg::LockStake {
+            hotkey,
+            amount,
+            duration,
+        } => do_lock_stake(deps, env, info, hotkey, amount, duration),
         ExecuteMsg::ServeAxon {
             netuid,
             version,
@@ -358,9 +402,20 @@ pub fn execute(
             coldkey,
         ),
         ExecuteMsg::RootRegister { hotkey } => do_root_register(deps, env, info, hotkey),
+        ExecuteMsg::Deregister { netuid, hotkey } => {
+            do_deregister(deps, env, info, netuid, hotkey)
+        }
         ExecuteMsg::BurnedRegister { netuid, hotkey } => {
             do_burned_registration(deps, env, info, netuid, hotkey)
         }
+        ExecuteMsg::StakedRegister { netuid, hotkey } => {
+            do_staked_registration(deps, env, info, netuid, hotkey)
+        }
+        ExecuteMsg::Faucet {
+            block_number,
+            nonce,
+            work,
+        } => do_faucet(deps, env, info, block_number, nonce, work),
 
         ExecuteMsg::RegisterNetwork {} => user_add_network(deps, env, info),
         ExecuteMsg::DissolveNetwork { netuid } => user_remove_network(deps, env, info, netuid),
@@ -373,10 +428,19 @@ pub fn execute(
         ExecuteMsg::SudoSetDefaultTake { default_take } => {
             do_sudo_set_default_take(deps, env, info, default_take)
         }
+        ExecuteMsg::SudoSetRootMinStake { min_stake } => {
+            do_sudo_set_root_min_stake(deps, env, info, min_stake)
+        }
+        ExecuteMsg::SudoSetRootAutoDelegate { auto_delegate } => {
+            do_sudo_set_root_auto_delegate(deps, env, info, auto_delegate)
+        }
         ExecuteMsg::SudoSetServingRateLimit {
             netuid,
             serving_rate_limit,
         } => do_sudo_set_serving_rate_limit(deps, env, info, netuid, serving_rate_limit),
+        ExecuteMsg::SudoSetServingAllowPrivateIps { netuid, allow } => {
+            do_sudo_set_serving_allow_private_ips(deps, env, info, netuid, allow)
+        }
         ExecuteMsg::SudoSetTxRateLimit { tx_rate_limit } => {
             do_sudo_set_tx_rate_limit(deps, env, info, tx_rate_limit)
         }
@@ -530,6 +594,42 @@ pub fn execute(
         ExecuteMsg::SudoSetCommissionChange { change } => {
             do_sudo_set_commission_change(deps, env, info, change)
         },
+        ExecuteMsg::SudoSetBlocksPerDay { blocks_per_day } => {
+            do_sudo_set_blocks_per_day(deps, env, info, blocks_per_day)
+        },
+        ExecuteMsg::SudoSetMaxLockDuration { max_lock_duration } => {
+            do_sudo_set_max_lock_duration(deps, env, info, max_lock_duration)
+        },
+        ExecuteMsg::SudoSetMaxLockBonus { max_lock_bonus } => {
+            do_sudo_set_max_lock_bonus(deps, env, info, max_lock_bonus)
+        },
+        ExecuteMsg::SudoSetSlashing { netuid, slashing } => {
+            do_sudo_set_slashing(deps, env, info, netuid, slashing)
+        },
+        ExecuteMsg::SudoSetRegistrationFeeDestination { netuid, destination } => {
+            do_sudo_set_registration_fee_destination(deps, env, info, netuid, destination)
+        }
+        ExecuteMsg::SudoSetRegistrationMode { netuid, mode } => {
+            do_sudo_set_registration_mode(deps, env, info, netuid, mode)
+        }
+        ExecuteMsg::SudoSetAllowFaucet { allow } => do_sudo_set_allow_faucet(deps, env, info, allow),
+        ExecuteMsg::SudoSetFaucetConfig { config } => {
+            do_sudo_set_faucet_config(deps, env, info, config)
+        }
+        ExecuteMsg::SudoFundFaucet {} => do_sudo_fund_faucet(deps, env, info),
+        ExecuteMsg::SudoSetRegistrationStake {
+            netuid,
+            registration_stake,
+        } => do_sudo_set_registration_stake(deps, env, info, netuid, registration_stake),
+        ExecuteMsg::SudoSetRegistrationAllowlistEnabled { netuid, enabled } => {
+            do_sudo_set_registration_allowlist_enabled(deps, env, info, netuid, enabled)
+        }
+        ExecuteMsg::SudoAddToRegistrationAllowlist { netuid, addresses } => {
+            do_sudo_add_to_registration_allowlist(deps, env, info, netuid, addresses)
+        }
+        ExecuteMsg::SudoRemoveFromRegistrationAllowlist { netuid, addresses } => {
+            do_sudo_remove_from_registration_allowlist(deps, env, info, netuid, addresses)
+        }
     }
 }
 
@@ -543,9 +643,22 @@ pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractE
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::GetDelegates {} => to_json_binary(&get_delegates(deps)?),
+        QueryMsg::GetDelegates {
+            start_after,
+            limit,
+            order_by_stake,
+        } => to_json_binary(&get_delegates(deps, start_after, limit, order_by_stake)?),
         QueryMsg::GetDelegate { delegate } => to_json_binary(&get_delegate(deps, delegate)?),
-        QueryMsg::GetDelegated { delegatee } => to_json_binary(&get_delegated(deps, delegatee)?),
+        QueryMsg::GetDelegated {
+            delegatee,
+            start_after,
+            limit,
+        } => to_json_binary(&get_delegated(deps, delegatee, start_after, limit)?),
+        QueryMsg::GetNominators {
+            hotkey,
+            start_after,
+            limit,
+        } => to_json_binary(&get_nominators(deps, hotkey, start_after, limit)?),
         QueryMsg::GetNeuronsLite { netuid } => {
             to_json_binary(&get_neurons_lite(deps.storage, netuid)?)
         }
@@ -553,6 +666,9 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_json_binary(&get_neuron_lite(deps.storage, netuid, uid)?)
         }
         QueryMsg::GetNeurons { netuid } => to_json_binary(&get_neurons(deps.storage, netuid)?),
+        QueryMsg::GetPruningCandidates { netuid, limit } => to_json_binary(
+            &get_pruning_candidates(deps.storage, netuid, env.block.height, limit)?,
+        ),
         QueryMsg::GetNeuron { netuid, uid } => {
             to_json_binary(&get_neuron(deps.storage, netuid, uid)?)
         }
@@ -567,6 +683,26 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetStakeInfoForColdkeys { coldkeys } => {
             to_json_binary(&get_stake_info_for_coldkeys(deps, coldkeys)?)
         }
+        QueryMsg::GetRewardPreference { hotkey, coldkey } => {
+            let hotkey_address = deps.api.addr_validate(&hotkey)?;
+            let coldkey_address = deps.api.addr_validate(&coldkey)?;
+            to_json_binary(&query_reward_preference(deps.storage, &hotkey_address, &coldkey_address)?)
+        }
+        QueryMsg::GetClaimable { coldkey } => {
+            let coldkey_address = deps.api.addr_validate(&coldkey)?;
+            to_json_binary(&get_claimable_for_coldkey(deps.storage, &coldkey_address))
+        }
+        QueryMsg::GetStakeLock { hotkey, coldkey } => {
+            let hotkey_address = deps.api.addr_validate(&hotkey)?;
+            let coldkey_address = deps.api.addr_validate(&coldkey)?;
+            to_json_binary(&query_stake_lock(deps.storage, &hotkey_address, &coldkey_address)?)
+        }
+        QueryMsg::GetRewardsHistory {
+            hotkey,
+            coldkey,
+            start_after,
+            limit,
+        } => to_json_binary(&get_rewards_history(deps, hotkey, coldkey, start_after, limit)?),
         QueryMsg::GetNetworkRegistrationCost {} => to_json_binary(&get_network_lock_cost(
             deps.storage,
             deps.api,
@@ -581,6 +717,10 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let hotkey_address = deps.api.addr_validate(&hotkey)?;
             to_json_binary(&get_hotkey_owner(deps.storage, &hotkey_address)?)
         }
+        QueryMsg::GetHotkeyOwnerApproval { hotkey } => {
+            let hotkey_address = deps.api.addr_validate(&hotkey)?;
+            to_json_binary(&get_hotkey_owner_approval(deps.storage, &hotkey_address)?)
+        }
         QueryMsg::GetStakeForColdkeyAndHotkey { coldkey, hotkey } => {
             let coldkey_address = deps.api.addr_validate(&coldkey)?;
             let hotkey_address = deps.api.addr_validate(&hotkey)?;
@@ -623,6 +763,22 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetEmissionValueBySubnet { netuid } => {
             to_json_binary(&query_emission_value_by_subnet(deps, netuid)?)
         }
+        QueryMsg::GetRootMinStake {} => to_json_binary(&get_root_min_stake(deps.storage)),
+        QueryMsg::GetRootAutoDelegate {} => to_json_binary(&get_root_auto_delegate(deps.storage)),
+        QueryMsg::GetFaucetConfig {} => to_json_binary(&get_faucet_config(deps.storage)),
+        QueryMsg::GetFaucetReserve {} => to_json_binary(&get_faucet_reserve(deps.storage)),
+        QueryMsg::GetSlashing { netuid } => to_json_binary(&query_slashing(deps.storage, netuid)?),
+        QueryMsg::GetBlockSeed { block } => {
+            to_json_binary(&get_block_hash_from_u64(deps.storage, block).map(hash_to_vec))
+        }
+        QueryMsg::GetRegistrationAllowlist {
+            netuid,
+            start_after,
+            limit,
+        } => to_json_binary(&query_registration_allowlist(deps, netuid, start_after, limit)?),
+        QueryMsg::GetRegistrationFeeDestination { netuid } => {
+            to_json_binary(&get_registration_fee_destination(deps.storage, netuid))
+        }
         QueryMsg::GetAllSubnetNetuids {} => {
             to_json_binary(&query_all_subnet_netuids(deps.storage)?)
         }
@@ -702,6 +858,11 @@ pub fn get_subnet_owner(store: &dyn Storage, netuid: u16) -> StdResult<Option<St
     }
 }
 
+pub fn get_hotkey_owner_approval(store: &dyn Storage, hotkey: &Addr) -> StdResult<Option<String>> {
+    let coldkey = HOTKEY_OWNER_APPROVALS.may_load(store, hotkey)?;
+    Ok(coldkey.map(|coldkey| coldkey.to_string()))
+}
+
 pub fn get_hotkey_owner(store: &dyn Storage, hotkey: &Addr) -> StdResult<Option<String>> {
     let owner = OWNER.may_load(store, hotkey)?;
     if owner.is_some() {
@@ -718,7 +879,7 @@ pub fn query_stake_for_coldkey_and_hotkey(
 ) -> StdResult<Option<u64>> {
     let stake = STAKE.may_load(store, (hotkey, coldkey))?;
     if stake.is_some() {
-        Ok(Some(stake.unwrap()))
+        Ok(Some(get_stake_for_coldkey_and_hotkey(store, coldkey, hotkey)))
     } else {
         Ok(None)
     }
@@ -820,6 +981,51 @@ pub fn query_emission_value_by_subnet(deps: Deps, netuid: u16) -> StdResult<u64>
     Ok(emission_value)
 }
 
+pub fn query_reward_preference(
+    store: &dyn Storage,
+    hotkey: &Addr,
+    coldkey: &Addr,
+) -> StdResult<RewardPreference> {
+    let preference = REWARD_PREFERENCE
+        .may_load(store, (hotkey, coldkey))?
+        .unwrap_or(RewardPreference::Compound);
+    Ok(preference)
+}
+
+pub fn query_stake_lock(
+    store: &dyn Storage,
+    hotkey: &Addr,
+    coldkey: &Addr,
+) -> StdResult<Option<StakeLock>> {
+    let lock = STAKE_LOCKS.may_load(store, (hotkey, coldkey))?;
+    Ok(lock)
+}
+
+pub fn query_registration_allowlist(
+    deps: Deps,
+    netuid: u16,
+    start_after: Option<String>,
+    limit: Option<u16>,
+) -> StdResult<Vec<String>> {
+    let start_after = start_after
+        .map(|address| deps.api.addr_validate(&address))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let limit = limit.unwrap_or(32).min(MAX_ALLOWLIST_LIMIT) as usize;
+
+    REGISTRATION_ALLOWLIST
+        .prefix(netuid)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| key.map(|key| key.to_string()))
+        .collect()
+}
+
+pub fn query_slashing(store: &dyn Storage, netuid: u16) -> StdResult<Option<SlashingConfig>> {
+    let slashing = SLASHING.may_load(store, netuid)?;
+    Ok(slashing)
+}
+
 pub fn query_all_subnet_netuids(store: &dyn Storage) -> StdResult<Vec<u16>> {
     let netuids = NETWORKS_ADDED
         .range(store, None, None, Order::Ascending)
@@ -893,7 +1099,15 @@ pub fn query_get_total_stake_for_coldkey(
 ) -> StdResult<Option<u64>> {
     let stake = TOTAL_COLDKEY_STAKE.may_load(store, coldkey)?;
     if stake.is_some() {
-        Ok(Some(stake.unwrap()))
+        // Add not settled rewards of the nominator
+        let mut pending_rewards = 0u64;
+        for hotkey in STAKED_HOTKEYS
+            .prefix(coldkey)
+            .keys(store, None, None, Order::Ascending)
+        {
+            pending_rewards += get_pending_rewards_for_coldkey_and_hotkey(store, coldkey, &hotkey?);
+        }
+        Ok(Some(stake.unwrap() + pending_rewards))
     } else {
         Ok(None)
     }
@@ -911,7 +1125,8 @@ pub fn query_get_stake(store: &dyn Storage, hotkey: &Addr) -> StdResult<Vec<(Str
         .prefix(hotkey)
         .range(store, None, None, Order::Ascending)
         .map(|item| {
-            let (address, stake) = item.unwrap();
+            let (address, _) = item.unwrap();
+            let stake = get_stake_for_coldkey_and_hotkey(store, &address, hotkey);
             (address.to_string(), stake)
         })
         .collect::<Vec<(String, u64)>>();
@@ -971,7 +1186,7 @@ pub fn get_economy(
     let default_commission = Decimal::from_ratio(default_take, u16::MAX)
         .mul(Decimal::from_atomics(Uint128::from(100u64),0).unwrap());
     let commission_change = COMMISSION_CHANGE.load(store)?;
-    let blocks_per_year = 5256000u64;
+    let blocks_per_year = get_blocks_per_day(store) * 365;
     let total_issuance = TOTAL_ISSUANCE.load(store)?;
     let total_rewards = TOTAL_REWARDS.load(store)?;
 
@@ -1007,6 +1222,34 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
 
     // Only migrate if newer
     if storage_version.version.as_str() < CONTRACT_VERSION {
+        if BLOCKS_PER_DAY.may_load(deps.storage)?.is_none() {
+            BLOCKS_PER_DAY.save(deps.storage, &14400)?;
+        }
+        if TOTAL_CLAIMABLE.may_load(deps.storage)?.is_none() {
+            TOTAL_CLAIMABLE.save(deps.storage, &0)?;
+        }
+        if MAX_LOCK_DURATION.may_load(deps.storage)?.is_none() {
+            MAX_LOCK_DURATION.save(deps.storage, &(14400 * 365))?;
+            MAX_LOCK_BONUS.save(deps.storage, &u16::MAX)?;
+        }
+
+        // Build stake indexes for delegates and coldkeys
+        let delegates = DELEGATES
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<Addr>>>()?;
+        for delegate in delegates {
+            let stake = TOTAL_HOTKEY_STAKE.may_load(deps.storage, &delegate)?.unwrap_or_default();
+            DELEGATES_BY_STAKE.save(deps.storage, (stake, &delegate), &Empty {})?;
+        }
+        let stakes = STAKE
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<((Addr, Addr), u64)>>>()?;
+        for ((hotkey, coldkey), stake) in stakes {
+            if stake > 0 {
+                STAKED_HOTKEYS.save(deps.storage, (&coldkey, &hotkey), &Empty {})?;
+            }
+        }
+
         // Set contract to version to latest
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     } else {
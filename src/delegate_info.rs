@@ -1,48 +1,73 @@
 use crate::staking::{
     get_owning_coldkey_for_hotkey, get_stake_for_coldkey_and_hotkey, get_total_stake_for_hotkey,
 };
-use crate::state::{DELEGATES, DENOM, STAKE};
+use crate::state::{
+    DelegateIdentity, DelegateLimits, DELEGATES, DELEGATES_BY_STAKE, DELEGATE_IDENTITY, DELEGATE_LIMITS, DENOM, STAKE,
+    STAKED_HOTKEYS, TOTAL_HOTKEY_STAKE,
+};
 use crate::uids::{get_registered_networks_for_hotkey, get_uid_for_net_and_hotkey};
-use crate::utils::{get_emission_for_uid, get_tempo, get_validator_permit_for_uid};
+use crate::utils::{
+    get_blocks_per_day, get_emission_for_uid, get_tempo, get_validator_permit_for_uid,
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Deps, Order, StdResult, Storage};
+use cosmwasm_std::{Addr, Coin, Decimal, Deps, Order, StdResult, Storage, Uint128};
+use std::ops::Mul;
+use cw_storage_plus::Bound;
 use substrate_fixed::types::U64F64;
 extern crate alloc;
 use alloc::vec::Vec;
 
+// Max number of items returned by a page of the delegate queries.
+const MAX_DELEGATES_LIMIT: u16 = 64;
+const MAX_NOMINATORS_LIMIT: u16 = 256;
+
+// Nominators of a delegate are not included, they are paginated with GetNominators.
 #[cw_serde]
 pub struct DelegateInfo {
-    delegate: Addr,
-    take: u16,
-    nominators: Vec<(Addr, u64)>,
-    // map of nominator to stake amount
-    owner: Addr,
-    registrations: Vec<u16>,
+    pub delegate: Addr,
+    pub take: u16,
+    pub owner: Addr,
+    pub registrations: Vec<u16>,
     // Vec of netuid this delegate is registered on
-    validator_permits: Vec<u16>,
+    pub validator_permits: Vec<u16>,
     // Vec of netuid this delegate has validator permit on
-    return_per_giga: Coin,
+    pub return_per_giga: Coin,
     // Delegators current daily return per X tokens staked minus take fee
-    total_daily_return: Coin, // Delegators current daily return
+    pub total_daily_return: Coin, // Delegators current daily return
+    pub identity: Option<DelegateIdentity>, // Identity published by the delegate owner
+    pub limits: DelegateLimits, // Capacity and openness to new nominators
+    pub subnet_returns: Vec<DelegateSubnetReturn>, // Returns breakdown by netuid
+    pub apr: Decimal, // Delegators annualised return in percents minus take fee
+}
+
+#[cw_serde]
+pub struct DelegateSubnetReturn {
+    pub netuid: u16,
+    pub return_per_giga: Coin,
+    pub daily_return: Coin,
 }
 
 pub fn get_delegate_by_existing_account(store: &dyn Storage, delegate: &Addr) -> DelegateInfo {
-    let mut nominators = Vec::<(Addr, u64)>::new();
+    let owner = get_owning_coldkey_for_hotkey(store, &delegate);
+    let take: u16 = DELEGATES.load(store, &delegate).unwrap();
+    let denom = DENOM.load(store).unwrap();
 
-    for item in STAKE
-        .prefix(&delegate)
-        .range(store, None, None, Order::Ascending)
-    {
-        let (nominator, stake) = item.unwrap();
-        if stake == 0 {
-            continue;
+    // Delegators receive the emission minus the delegate take
+    let nominators_share =
+        U64F64::from_num(1) - U64F64::from_num(take) / U64F64::from_num(u16::MAX);
+    let blocks_per_day = U64F64::from_num(get_blocks_per_day(store));
+    let total_stake = U64F64::from_num(get_total_stake_for_hotkey(store, &delegate));
+    let return_per_giga = |emission_per_day: U64F64| -> U64F64 {
+        if total_stake > U64F64::from_num(0) {
+            (emission_per_day * nominators_share) / (total_stake / U64F64::from_num(1000000000))
+        } else {
+            U64F64::from_num(0)
         }
-        // Only add nominators with stake
-        nominators.push((nominator.clone(), stake.into()));
-    }
+    };
 
     let registrations = get_registered_networks_for_hotkey(store, &delegate);
     let mut validator_permits = Vec::<u16>::new();
+    let mut subnet_returns = Vec::<DelegateSubnetReturn>::new();
     let mut emissions_per_day: U64F64 = U64F64::from_num(0);
 
     for netuid in registrations.iter() {
@@ -56,37 +81,48 @@ pub fn get_delegate_by_existing_account(store: &dyn Storage, delegate: &Addr) ->
                 validator_permits.push((*netuid).into());
             }
 
+            let tempo = get_tempo(store, *netuid);
+            if tempo == 0 {
+                continue; // no epochs, no emission
+            }
             let emission = U64F64::from_num(get_emission_for_uid(store, *netuid, uid));
-            let tempo = U64F64::from_num(get_tempo(store, *netuid));
-            let epochs_per_day = U64F64::from_num(14400) / tempo;
-            emissions_per_day += emission * epochs_per_day;
+            let epochs_per_day = blocks_per_day / U64F64::from_num(tempo);
+            let emission_per_day = emission * epochs_per_day;
+            emissions_per_day += emission_per_day;
+
+            subnet_returns.push(DelegateSubnetReturn {
+                netuid: *netuid,
+                return_per_giga: Coin::new(
+                    return_per_giga(emission_per_day).to_num::<u128>(),
+                    denom.clone(),
+                ),
+                daily_return: Coin::new(emission_per_day.to_num::<u128>(), denom.clone()),
+            });
         }
     }
 
-    let owner = get_owning_coldkey_for_hotkey(store, &delegate);
-    let take: u16 = DELEGATES.load(store, &delegate).unwrap();
-
-    let total_stake = U64F64::from_num(get_total_stake_for_hotkey(store, &delegate));
-
-    let mut return_per_giga = U64F64::from_num(0);
-
+    let mut apr = Decimal::zero();
     if total_stake > U64F64::from_num(0) {
-        // TODO rewrite this to Decimal and load take from store
-        return_per_giga =
-            (emissions_per_day * U64F64::from_num(0.8)) / (total_stake / U64F64::from_num(1000000000));
+        let nominators_yearly_return = emissions_per_day * nominators_share * U64F64::from_num(365);
+        apr = Decimal::from_ratio(nominators_yearly_return.to_num::<u128>(), total_stake.to_num::<u128>())
+            .mul(Decimal::from_atomics(Uint128::from(100u64), 0).unwrap());
     }
 
-    let denom = DENOM.load(store).unwrap();
+    let identity = DELEGATE_IDENTITY.may_load(store, delegate).unwrap();
+    let limits = DELEGATE_LIMITS.may_load(store, delegate).unwrap().unwrap_or_default();
 
     return DelegateInfo {
         delegate: delegate.clone(),
         take,
-        nominators,
         owner: owner.clone(),
         registrations: registrations.iter().map(|x| *x).collect(),
         validator_permits,
-        return_per_giga: Coin::new(U64F64::to_num::<u128>(return_per_giga), denom.clone()),
+        return_per_giga: Coin::new(return_per_giga(emissions_per_day).to_num::<u128>(), denom.clone()),
         total_daily_return: Coin::new(U64F64::to_num::<u128>(emissions_per_day), denom),
+        identity,
+        limits,
+        subnet_returns,
+        apr,
     };
 }
 
@@ -100,40 +136,104 @@ pub fn get_delegate(deps: Deps, delegate: String) -> StdResult<Option<DelegateIn
     return Ok(Some(delegate_info));
 }
 
-// TODO add pagination and limit
-pub fn get_delegates(deps: Deps) -> StdResult<Vec<DelegateInfo>> {
-    let mut delegates = Vec::<DelegateInfo>::new();
-    for item in DELEGATES
-        .range(deps.storage, None, None, Order::Ascending)
-        .into_iter()
-    {
-        let (delegate, _) = item?;
-        let delegate_info = get_delegate_by_existing_account(deps.storage, &delegate);
-        delegates.push(delegate_info);
-    }
+pub fn get_delegates(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u16>,
+    order_by_stake: Option<bool>,
+) -> StdResult<Vec<DelegateInfo>> {
+    let start_after = start_after
+        .map(|delegate| deps.api.addr_validate(&delegate))
+        .transpose()?;
+    let delegates_limit = limit.unwrap_or(32).min(MAX_DELEGATES_LIMIT) as usize;
+
+    let delegates = if order_by_stake.unwrap_or(false) {
+        // Descending by total stake, the cursor is resolved to its current stake.
+        let end = start_after.as_ref().map(|delegate| {
+            Bound::exclusive((
+                TOTAL_HOTKEY_STAKE
+                    .may_load(deps.storage, delegate)
+                    .unwrap()
+                    .unwrap_or_default(),
+                delegate,
+            ))
+        });
+        DELEGATES_BY_STAKE
+            .keys(deps.storage, None, end, Order::Descending)
+            .take(delegates_limit)
+            .map(|item| item.map(|(_, delegate)| delegate))
+            .collect::<StdResult<Vec<Addr>>>()?
+    } else {
+        let start = start_after.as_ref().map(Bound::exclusive);
+        DELEGATES
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(delegates_limit)
+            .collect::<StdResult<Vec<Addr>>>()?
+    };
 
-    return Ok(delegates);
+    Ok(delegates
+        .iter()
+        .map(|delegate| get_delegate_by_existing_account(deps.storage, delegate))
+        .collect())
 }
 
-// TODO add pagination and limit
-pub fn get_delegated(deps: Deps, delegatee: String) -> StdResult<Vec<(DelegateInfo, u64)>> {
+pub fn get_delegated(
+    deps: Deps,
+    delegatee: String,
+    start_after: Option<String>,
+    limit: Option<u16>,
+) -> StdResult<Vec<(DelegateInfo, u64)>> {
     let delegatee = deps.api.addr_validate(&delegatee)?;
+    let start_after = start_after
+        .map(|delegate| deps.api.addr_validate(&delegate))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let delegates_limit = limit.unwrap_or(32).min(MAX_DELEGATES_LIMIT) as usize;
+
     let mut delegates: Vec<(DelegateInfo, u64)> = Vec::new();
-    // TODO iterator over all delegates?
-    for item in DELEGATES
-        .range(deps.storage, None, None, Order::Ascending)
-        .into_iter()
+    for item in STAKED_HOTKEYS
+        .prefix(&delegatee)
+        .keys(deps.storage, start, None, Order::Ascending)
     {
-        let (delegate, _) = item?;
+        let delegate = item?;
+        if !DELEGATES.has(deps.storage, &delegate) {
+            continue; // Stake on own non delegate hotkey
+        }
         let staked_to_this_delegatee =
             get_stake_for_coldkey_and_hotkey(deps.storage, &delegatee, &delegate);
-        if staked_to_this_delegatee == 0 {
-            continue; // No stake to this delegate
-        }
         // Staked to this delegate, so add to list
         let delegate_info = get_delegate_by_existing_account(deps.storage, &delegate);
-        delegates.push((delegate_info, staked_to_this_delegatee.into()));
+        delegates.push((delegate_info, staked_to_this_delegatee));
+        if delegates.len() == delegates_limit {
+            break;
+        }
     }
 
-    return Ok(delegates);
+    Ok(delegates)
+}
+
+pub fn get_nominators(
+    deps: Deps,
+    hotkey: String,
+    start_after: Option<String>,
+    limit: Option<u16>,
+) -> StdResult<Vec<(Addr, u64)>> {
+    let hotkey = deps.api.addr_validate(&hotkey)?;
+    let start_after = start_after
+        .map(|nominator| deps.api.addr_validate(&nominator))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let nominators_limit = limit.unwrap_or(32).min(MAX_NOMINATORS_LIMIT) as usize;
+
+    STAKE
+        .prefix(&hotkey)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| !matches!(item, Ok((_, 0))))
+        .take(nominators_limit)
+        .map(|item| {
+            let (nominator, _) = item?;
+            let stake = get_stake_for_coldkey_and_hotkey(deps.storage, &nominator, &hotkey);
+            Ok((nominator, stake))
+        })
+        .collect()
 }
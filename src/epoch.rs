@@ -10,7 +10,7 @@ use crate::math::{
     row_sum_sparse, vec_fixed64_to_fixed32, vec_mask_sparse_matrix, vec_max_upscale_to_u16, vecdiv,
     weighted_median_col_sparse,
 };
-use crate::staking::get_total_stake_for_hotkey;
+use crate::staking::{get_boosted_stake_for_hotkey, get_total_stake_for_hotkey};
 use crate::state::{
     ACTIVE, BONDS, CONSENSUS, DIVIDENDS, EMISSION, INCENTIVE, KEYS, PRUNING_SCORES, RANK, TRUST,
     VALIDATOR_PERMIT, VALIDATOR_TRUST, WEIGHTS,
@@ -125,8 +125,18 @@ pub fn epoch(
     //     max_allowed_validators
     // ));
 
+    // Locked stake counts with its bonus for validator permits.
+    let mut boosted_stake_64: Vec<I64F64> = vec![I64F64::from_num(0.0); n as usize];
+    for (uid_i, hotkey) in hotkeys.iter() {
+        boosted_stake_64[*uid_i as usize] =
+            I64F64::from_num(get_boosted_stake_for_hotkey(store, hotkey));
+    }
+    inplace_normalize_64(&mut boosted_stake_64);
+    let boosted_stake: Vec<I32F32> = vec_fixed64_to_fixed32(boosted_stake_64);
+
     // Get new validator permits.
-    let new_validator_permits: Vec<bool> = is_topk(&stake, max_allowed_validators as usize);
+    let new_validator_permits: Vec<bool> =
+        is_topk(&boosted_stake, max_allowed_validators as usize);
     // api.debug(&format!(
     //     "⚪️ new_validator_permits: {:?}",
     //     new_validator_permits
@@ -76,6 +76,9 @@ pub enum ContractError {
     #[error("Thrown if the supplied pow hash seal does not match the supplied work.")]
     InvalidSeal {},
 
+    #[error("Thrown if the supplied pow work was already used.")]
+    WorkRepeated {},
+
     #[error("Thrown if the vaule is invalid for MaxAllowedUids.")]
     MaxAllowedUIdsNotAllowed {},
 
@@ -149,6 +152,12 @@ pub enum ContractError {
     #[error("Thrown when the faucet is disabled")]
     FaucetDisabled {},
 
+    #[error("Thrown when the faucet reserve can't cover the payout")]
+    FaucetReserveDepleted {},
+
+    #[error("Thrown when the coldkey used the faucet within the cooldown")]
+    FaucetCooldown {},
+
     #[error("Thrown when key not subnet owner")]
     NotSubnetOwner {},
 
@@ -175,4 +184,42 @@ pub enum ContractError {
 
     #[error("Thrown when commission change disabled for this verse")]
     CommissionChangeDisabled {},
+
+    #[error("Thrown when the hotkey is not a delegate")]
+    NotDelegate {},
+
+    #[error("Thrown when there is nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Thrown when the stake to remove is locked")]
+    StakeLocked {},
+
+    #[error("Thrown when the stake lock duration is zero or above the max lock duration")]
+    InvalidLockDuration {},
+
+    #[error("Thrown when the coldkey locks more stake than it has on the hotkey")]
+    NotEnoughStakeToLock {},
+
+    #[error("Thrown when stake allocations are empty, zero or don't match the paid funds")]
+    InvalidAllocations {},
+
+    #[error("Thrown when the delegate is closed to new nominators")]
+    DelegateClosed {},
+
+    #[error("Thrown when the stake exceeds the delegate max delegated stake")]
+    DelegateCapacityExceeded {},
+
+    #[error("Thrown when the hotkey has not approved the coldkey as its owner")]
+    HotkeyOwnershipNotApproved {},
+
+    #[error("Thrown when the subnet registration mode doesn't allow this registration")]
+    RegistrationModeDisabled {},
+
+    #[error(
+        "Thrown when neither the hotkey nor the coldkey is in the subnet registration allowlist"
+    )]
+    NotInRegistrationAllowlist {},
+
+    #[error("Thrown when the contract balance doesn't cover the stake, claimable, locked and faucet balances")]
+    ContractBalanceBelowReserved {},
 }
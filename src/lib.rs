@@ -10,6 +10,7 @@ pub mod neuron_info;
 mod registration;
 mod root;
 mod serving;
+mod slashing;
 pub mod stake_info;
 mod staking;
 pub mod state;
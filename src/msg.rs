@@ -2,7 +2,10 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Uint128, Coin, Decimal};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use crate::state::Metadata;
+use crate::state::{
+    DelegateIdentity, DelegateLimits, Metadata, RegistrationFeeDestination, RegistrationMode,
+    FaucetConfig, RewardPreference, SlashingConfig,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {}
@@ -26,14 +29,45 @@ pub enum ExecuteMsg {
     AddStake {
         hotkey: String,
     },
+    AddStakeFor {
+        hotkey: String,
+        beneficiary_coldkey: String,
+    },
     RemoveStake {
         hotkey: String,
         amount: u64,
     },
+    AddStakeMulti {
+        allocations: Vec<(String, u64)>,
+    },
+    RemoveStakeMulti {
+        allocations: Vec<(String, u64)>,
+    },
     SetDelegateCommission {
         hotkey: String,
         commission: String,
     },
+    SetDelegateIdentity {
+        hotkey: String,
+        identity: DelegateIdentity,
+    },
+    SetDelegateLimits {
+        hotkey: String,
+        limits: DelegateLimits,
+    },
+    SetRewardPreference {
+        hotkey: String,
+        preference: RewardPreference,
+    },
+    ApproveHotkeyOwner {
+        coldkey: String,
+    },
+    Claim {},
+    LockStake {
+        hotkey: String,
+        amount: u64,
+        duration: u64,
+    },
     ServeAxon {
         netuid: u16,
         version: u32,
@@ -59,6 +93,10 @@ pub enum ExecuteMsg {
         hotkey: String,
         coldkey: String,
     },
+    Deregister {
+        netuid: u16,
+        hotkey: Option<String>,
+    },
     RootRegister {
         hotkey: String,
     },
@@ -66,6 +104,15 @@ pub enum ExecuteMsg {
         netuid: u16,
         hotkey: String,
     },
+    StakedRegister {
+        netuid: u16,
+        hotkey: String,
+    },
+    Faucet {
+        block_number: u64,
+        nonce: u64,
+        work: Vec<u8>,
+    },
 
     RegisterNetwork {},
     DissolveNetwork {
@@ -80,10 +127,20 @@ pub enum ExecuteMsg {
     SudoSetDefaultTake {
         default_take: u16,
     },
+    SudoSetRootMinStake {
+        min_stake: u64,
+    },
+    SudoSetRootAutoDelegate {
+        auto_delegate: bool,
+    },
     SudoSetServingRateLimit {
         netuid: u16,
         serving_rate_limit: u64,
     },
+    SudoSetServingAllowPrivateIps {
+        netuid: u16,
+        allow: bool,
+    },
     SudoSetTxRateLimit {
         tx_rate_limit: u64,
     },
@@ -234,6 +291,50 @@ pub enum ExecuteMsg {
     SudoSetCommissionChange {
         change: bool,
     },
+    SudoSetBlocksPerDay {
+        blocks_per_day: u64,
+    },
+    SudoSetMaxLockDuration {
+        max_lock_duration: u64,
+    },
+    SudoSetMaxLockBonus {
+        max_lock_bonus: u16,
+    },
+    SudoSetSlashing {
+        netuid: u16,
+        slashing: Option<SlashingConfig>,
+    },
+    SudoSetRegistrationFeeDestination {
+        netuid: u16,
+        destination: RegistrationFeeDestination,
+    },
+    SudoSetRegistrationMode {
+        netuid: u16,
+        mode: RegistrationMode,
+    },
+    SudoSetAllowFaucet {
+        allow: bool,
+    },
+    SudoSetFaucetConfig {
+        config: FaucetConfig,
+    },
+    SudoFundFaucet {},
+    SudoSetRegistrationStake {
+        netuid: u16,
+        registration_stake: u64,
+    },
+    SudoSetRegistrationAllowlistEnabled {
+        netuid: u16,
+        enabled: bool,
+    },
+    SudoAddToRegistrationAllowlist {
+        netuid: u16,
+        addresses: Vec<String>,
+    },
+    SudoRemoveFromRegistrationAllowlist {
+        netuid: u16,
+        addresses: Vec<String>,
+    },
 }
 
 #[cw_serde]
@@ -245,11 +346,25 @@ pub enum SudoMsg {
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(Vec<crate::delegate_info::DelegateInfo>)]
-    GetDelegates {},
+    GetDelegates {
+        start_after: Option<String>,
+        limit: Option<u16>,
+        order_by_stake: Option<bool>,
+    },
     #[returns(Option<crate::delegate_info::DelegateInfo>)]
     GetDelegate { delegate: String },
     #[returns(Vec<(crate::delegate_info::DelegateInfo, u64)>)]
-    GetDelegated { delegatee: String },
+    GetDelegated {
+        delegatee: String,
+        start_after: Option<String>,
+        limit: Option<u16>,
+    },
+    #[returns(Vec<(cosmwasm_std::Addr, u64)>)]
+    GetNominators {
+        hotkey: String,
+        start_after: Option<String>,
+        limit: Option<u16>,
+    },
 
     #[returns(Vec<crate::neuron_info::NeuronInfoLite>)]
     GetNeuronsLite { netuid: u16 },
@@ -257,6 +372,8 @@ pub enum QueryMsg {
     GetNeuronLite { netuid: u16, uid: u16 },
     #[returns(Vec<crate::neuron_info::NeuronInfo>)]
     GetNeurons { netuid: u16 },
+    #[returns(Vec<crate::neuron_info::PruningCandidate>)]
+    GetPruningCandidates { netuid: u16, limit: Option<u16> },
     #[returns(Option<crate::neuron_info::NeuronInfo>)]
     GetNeuron { netuid: u16, uid: u16 },
 
@@ -271,6 +388,19 @@ pub enum QueryMsg {
     GetStakeInfoForColdkey { coldkey: String },
     #[returns(Vec<crate::stake_info::StakeInfo>)]
     GetStakeInfoForColdkeys { coldkeys: Vec<String> },
+    #[returns(RewardPreference)]
+    GetRewardPreference { hotkey: String, coldkey: String },
+    #[returns(u64)]
+    GetClaimable { coldkey: String },
+    #[returns(Option<crate::state::StakeLock>)]
+    GetStakeLock { hotkey: String, coldkey: String },
+    #[returns(crate::stake_info::RewardsHistory)]
+    GetRewardsHistory {
+        hotkey: String,
+        coldkey: String,
+        start_after: Option<u64>,
+        limit: Option<u16>,
+    },
 
     #[returns(Option<u64>)]
     GetTotalStakeForHotkey { address: String },
@@ -281,6 +411,8 @@ pub enum QueryMsg {
 
     #[returns(Option<String>)]
     GetHotkeyOwner { hotkey: String },
+    #[returns(Option<String>)]
+    GetHotkeyOwnerApproval { hotkey: String },
     #[returns(Option<u16>)]
     GetUidForHotkeyOnSubnet { hotkey: String, netuid: u16 },
     #[returns(Option<Vec<u16>>)]
@@ -308,6 +440,26 @@ pub enum QueryMsg {
     GetTempo { netuid: u16 },
     #[returns(u64)]
     GetEmissionValueBySubnet { netuid: u16 },
+    #[returns(u64)]
+    GetRootMinStake {},
+    #[returns(bool)]
+    GetRootAutoDelegate {},
+    #[returns(FaucetConfig)]
+    GetFaucetConfig {},
+    #[returns(u64)]
+    GetFaucetReserve {},
+    #[returns(Option<SlashingConfig>)]
+    GetSlashing { netuid: u16 },
+    #[returns(Option<Vec<u8>>)]
+    GetBlockSeed { block: u64 },
+    #[returns(Vec<String>)]
+    GetRegistrationAllowlist {
+        netuid: u16,
+        start_after: Option<String>,
+        limit: Option<u16>,
+    },
+    #[returns(crate::state::RegistrationFeeDestination)]
+    GetRegistrationFeeDestination { netuid: u16 },
 
     #[returns(u64)]
     GetNetworkRegistrationCost {},
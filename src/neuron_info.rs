@@ -1,65 +1,72 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Order, StdResult, Storage};
 
+use crate::registration::get_uid_with_min_pruning_score;
 use crate::root::if_subnet_exist;
 use crate::serving::{get_axon_info, get_prometheus_info};
-use crate::state::{AxonInfo, PrometheusInfo, BONDS, OWNER, STAKE, WEIGHTS};
+use crate::staking::get_stake_for_coldkey_and_hotkey;
+use crate::state::{
+    AxonInfo, PrometheusInfo, BLOCK_AT_REGISTRATION, BONDS, KEYS, OWNER, STAKE, WEIGHTS,
+};
 use crate::uids::{get_hotkey_for_net_and_uid, get_subnetwork_n};
 use crate::utils::{
     get_active_for_uid, get_consensus_for_uid, get_dividends_for_uid, get_emission_for_uid,
-    get_incentive_for_uid, get_last_update_for_uid, get_pruning_score_for_uid, get_rank_for_uid,
-    get_trust_for_uid, get_validator_permit_for_uid, get_validator_trust_for_uid,
+    get_immunity_period, get_incentive_for_uid, get_last_update_for_uid, get_pruning_score_for_uid,
+    get_rank_for_uid, get_trust_for_uid, get_validator_permit_for_uid, get_validator_trust_for_uid,
 };
 
+// Max number of items returned by the pruning candidates query.
+const MAX_PRUNING_CANDIDATES_LIMIT: u16 = 256;
+
 #[cw_serde]
 pub struct NeuronInfo {
-    hotkey: Addr,
-    coldkey: Addr,
-    uid: u16,
-    netuid: u16,
-    active: bool,
-    axon_info: AxonInfo,
-    prometheus_info: PrometheusInfo,
-    stake: Vec<(Addr, u64)>,
+    pub hotkey: Addr,
+    pub coldkey: Addr,
+    pub uid: u16,
+    pub netuid: u16,
+    pub active: bool,
+    pub axon_info: AxonInfo,
+    pub prometheus_info: PrometheusInfo,
+    pub stake: Vec<(Addr, u64)>,
     // map of coldkey to stake on this neuron/hotkey (includes delegations)
-    rank: u16,
-    emission: u64,
-    incentive: u16,
-    consensus: u16,
-    trust: u16,
-    validator_trust: u16,
-    dividends: u16,
-    last_update: u64,
-    validator_permit: bool,
-    weights: Vec<(u16, u16)>,
+    pub rank: u16,
+    pub emission: u64,
+    pub incentive: u16,
+    pub consensus: u16,
+    pub trust: u16,
+    pub validator_trust: u16,
+    pub dividends: u16,
+    pub last_update: u64,
+    pub validator_permit: bool,
+    pub weights: Vec<(u16, u16)>,
     // Vec of (uid, weight)
-    bonds: Vec<(u16, u16)>,
+    pub bonds: Vec<(u16, u16)>,
     // Vec of (uid, bond)
-    pruning_score: u16,
+    pub pruning_score: u16,
 }
 
 #[cw_serde]
 pub struct NeuronInfoLite {
-    hotkey: Addr,
-    coldkey: Addr,
-    uid: u16,
-    netuid: u16,
-    active: bool,
-    axon_info: AxonInfo,
-    prometheus_info: PrometheusInfo,
-    stake: Vec<(Addr, u64)>,
+    pub hotkey: Addr,
+    pub coldkey: Addr,
+    pub uid: u16,
+    pub netuid: u16,
+    pub active: bool,
+    pub axon_info: AxonInfo,
+    pub prometheus_info: PrometheusInfo,
+    pub stake: Vec<(Addr, u64)>,
     // map of coldkey to stake on this neuron/hotkey (includes delegations)
-    rank: u16,
-    emission: u64,
-    incentive: u16,
-    consensus: u16,
-    trust: u16,
-    validator_trust: u16,
-    dividends: u16,
-    last_update: u64,
-    validator_permit: bool,
+    pub rank: u16,
+    pub emission: u64,
+    pub incentive: u16,
+    pub consensus: u16,
+    pub trust: u16,
+    pub validator_trust: u16,
+    pub dividends: u16,
+    pub last_update: u64,
+    pub validator_permit: bool,
     // has no weights or bonds
-    pruning_score: u16,
+    pub pruning_score: u16,
 }
 
 pub fn get_neurons(store: &dyn Storage, netuid: u16) -> StdResult<Vec<NeuronInfo>> {
@@ -76,7 +83,7 @@ pub fn get_neurons(store: &dyn Storage, netuid: u16) -> StdResult<Vec<NeuronInfo
         let _neuron = get_neuron_subnet_exists(store, netuid, uid)?;
         let neuron;
         if _neuron.is_none() {
-            break; // No more neurons
+            continue; // Vacant uid left by a deregistered neuron
         } else {
             // No error, hotkey was registered
             neuron = _neuron.expect("Neuron should exist");
@@ -133,9 +140,13 @@ fn get_neuron_subnet_exists(
 
     let stake = STAKE
         .prefix(&hotkey)
-        .range(store, None, None, Order::Ascending)
-        .map(|item| item.map(|(address, stake)| (address, stake.into())))
-        .collect::<StdResult<_>>()?;
+        .keys(store, None, None, Order::Ascending)
+        .map(|item| {
+            let coldkey = item?;
+            let stake = get_stake_for_coldkey_and_hotkey(store, &coldkey, &hotkey);
+            Ok((coldkey, stake))
+        })
+        .collect::<StdResult<Vec<(Addr, u64)>>>()?;
 
     let neuron = NeuronInfo {
         hotkey: hotkey.clone(),
@@ -206,8 +217,13 @@ fn get_neuron_lite_subnet_exists(
 
     let stake = STAKE
         .prefix(&hotkey)
-        .range(store, None, None, Order::Ascending)
-        .collect::<Result<Vec<(Addr, u64)>, _>>()?;
+        .keys(store, None, None, Order::Ascending)
+        .map(|item| {
+            let coldkey = item?;
+            let stake = get_stake_for_coldkey_and_hotkey(store, &coldkey, &hotkey);
+            Ok((coldkey, stake))
+        })
+        .collect::<StdResult<Vec<(Addr, u64)>>>()?;
 
     let neuron = NeuronInfoLite {
         hotkey: hotkey.clone(),
@@ -246,7 +262,7 @@ pub fn get_neurons_lite(store: &dyn Storage, netuid: u16) -> StdResult<Vec<Neuro
         let _neuron = get_neuron_lite_subnet_exists(store, netuid, uid)?;
         let neuron;
         if _neuron.is_none() {
-            break; // No more neurons
+            continue; // Vacant uid left by a deregistered neuron
         } else {
             // No error, hotkey was registered
             neuron = _neuron.expect("Neuron should exist");
@@ -269,3 +285,85 @@ pub fn get_neuron_lite(
     let neuron = get_neuron_lite_subnet_exists(store, netuid, uid);
     neuron
 }
+
+#[cw_serde]
+pub struct PruningCandidate {
+    pub uid: u16,
+    // None for a vacant slot left by a deregistered neuron
+    pub hotkey: Option<Addr>,
+    pub pruning_score: u16,
+    pub block_at_registration: u64,
+    pub immunity_remaining: u64,
+    pub emission: u64,
+}
+
+// Returns the neurons of the subnet in the order the registrations replace them, see
+// registration::get_neuron_to_prune. Vacant slots go first, then neurons out of the immunity period
+// by pruning score (ties go to the highest uid), then immune neurons by pruning score (ties go to
+// the lowest uid). Neurons out of the immunity period with the max pruning score go last. The first
+// candidate is always the neuron the next registration replaces.
+//
+pub fn get_pruning_candidates(
+    store: &dyn Storage,
+    netuid: u16,
+    current_block: u64,
+    limit: Option<u16>,
+) -> StdResult<Vec<PruningCandidate>> {
+    if !if_subnet_exist(store, netuid) {
+        return Ok(Vec::new());
+    }
+
+    let immunity_period = get_immunity_period(store, netuid) as u64;
+    let mut candidates: Vec<((u8, u16, i32), PruningCandidate)> = Vec::new();
+    for uid in 0..get_subnetwork_n(store, netuid) {
+        let hotkey = KEYS.may_load(store, (netuid, uid))?;
+        let pruning_score = get_pruning_score_for_uid(store, netuid, uid);
+        let block_at_registration = BLOCK_AT_REGISTRATION
+            .may_load(store, (netuid, uid))?
+            .unwrap_or_default();
+        let immunity_remaining = match hotkey {
+            Some(_) => {
+                immunity_period.saturating_sub(current_block.saturating_sub(block_at_registration))
+            }
+            None => 0,
+        };
+
+        let order = match (&hotkey, immunity_remaining) {
+            (None, _) => (0, 0, uid as i32),
+            (Some(_), 0) if pruning_score < u16::MAX => (1, pruning_score, -(uid as i32)),
+            (Some(_), 0) => (3, pruning_score, -(uid as i32)),
+            (Some(_), _) => (2, pruning_score, uid as i32),
+        };
+        candidates.push((
+            order,
+            PruningCandidate {
+                uid,
+                hotkey,
+                pruning_score,
+                block_at_registration,
+                immunity_remaining,
+                emission: get_emission_for_uid(store, netuid, uid),
+            },
+        ));
+    }
+    candidates.sort_by_key(|(order, _)| *order);
+
+    // Without vacant slots the next replaced neuron comes from the registration selection itself,
+    // it differs from the ordering when no neuron out of the immunity period can be pruned.
+    if matches!(candidates.first(), Some((_, candidate)) if candidate.hotkey.is_some()) {
+        let uid_to_prune = get_uid_with_min_pruning_score(store, netuid, current_block);
+        if let Some(index) = candidates.iter().position(|(_, c)| c.uid == uid_to_prune) {
+            let candidate = candidates.remove(index);
+            candidates.insert(0, candidate);
+        }
+    }
+
+    let limit = limit
+        .unwrap_or(MAX_PRUNING_CANDIDATES_LIMIT)
+        .min(MAX_PRUNING_CANDIDATES_LIMIT) as usize;
+    Ok(candidates
+        .into_iter()
+        .take(limit)
+        .map(|(_, candidate)| candidate)
+        .collect())
+}
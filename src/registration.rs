@@ -1,19 +1,32 @@
 use crate::root::{get_root_netuid, if_subnet_allows_registration, if_subnet_exist};
-use crate::staking::coldkey_owns_hotkey;
-use crate::staking::{create_account_if_non_existent, increase_stake_on_coldkey_hotkey_account};
+use crate::staking::{coldkey_owns_hotkey, ensure_hotkey_owner_approved, get_owning_coldkey_for_hotkey};
+use crate::staking::{
+    add_stake_lock, create_account_if_non_existent, get_locked_stake,
+    increase_stake_on_coldkey_hotkey_account,
+};
 use crate::state::{
-    BURN_REGISTRATIONS_THIS_INTERVAL, DENOM, POW_REGISTRATIONS_THIS_INTERVAL,
-    REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, UIDS, USED_WORK,
+    ALLOW_FAUCET, AXONS, BLOCK_SEEDS, BONDS, BURN_REGISTRATIONS_THIS_INTERVAL, DENOM,
+    FAUCET_LAST_PAYOUT, FAUCET_RESERVE, POW_REGISTRATIONS_THIS_INTERVAL,
+    PROMETHEUS, REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, REGISTRATION_ALLOWLIST,
+    RegistrationFeeDestination, RegistrationMode, SUBNET_OWNER, UIDS, USED_WORK,
+    USED_WORK_PRUNE_CURSOR, WEIGHTS,
+};
+use crate::uids::{
+    append_neuron, get_subnetwork_n, get_uid_for_net_and_hotkey, get_vacant_uid, remove_neuron,
+    replace_neuron,
 };
-use crate::uids::{append_neuron, get_subnetwork_n, replace_neuron};
 use crate::utils::{
-    burn_tokens, ensure_root, get_burn_as_u64, get_difficulty_as_u64, get_immunity_period,
+    burn_tokens, ensure_root, ensure_subnet_owner_or_root, get_burn_as_u64, get_difficulty_as_u64,
+    get_faucet_config, get_faucet_reserve, get_immunity_period,
     get_max_allowed_uids, get_max_registrations_per_block, get_neuron_block_at_registration,
-    get_pruning_score_for_uid, get_registrations_this_block, get_registrations_this_interval,
-    get_target_registrations_per_interval, increase_rao_recycled, set_pruning_score_for_uid,
+    get_pruning_score_for_uid, get_registration_fee_destination, get_registration_mode,
+    get_registration_stake, get_registrations_this_block, is_registration_allowlist_enabled, get_registrations_this_interval,
+    get_target_registrations_per_interval, increase_rao_recycled, set_active_for_uid,
+    set_pruning_score_for_uid, set_validator_permit_for_uid,
 };
 use crate::ContractError;
-use cosmwasm_std::{ensure, Api, DepsMut, Env, MessageInfo, StdResult, Storage};
+use cosmwasm_std::{coins, ensure, Addr, Api, BankMsg, DepsMut, Env, MessageInfo, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
 use cw_utils::must_pay;
 
 use primitive_types::{H256, U256};
@@ -21,6 +34,11 @@ use primitive_types::{H256, U256};
 use cyber_std::Response;
 use sp_core_hashing::{keccak_256, sha2_256};
 
+// Number of recent blocks the PoW registration work may commit to.
+const WORK_BLOCKS_WINDOW: u64 = 3;
+// Max number of used work entries checked for expiration in a block step.
+const USED_WORK_PRUNE_LIMIT: usize = 64;
+
 pub fn do_sudo_registration(
     deps: DepsMut,
     env: Env,
@@ -40,8 +58,6 @@ pub fn do_sudo_registration(
         netuid, hotkey, coldkey
     ));
 
-    ensure_root(deps.storage, &info.sender)?;
-
     ensure!(
         netuid != get_root_netuid(),
         ContractError::OperationNotPermittedOnRootSubnet {}
@@ -52,11 +68,21 @@ pub fn do_sudo_registration(
         ContractError::NetworkDoesNotExist {}
     );
 
+    // Root registers on any subnet, owners only on their permissioned subnets.
+    ensure_subnet_owner_or_root(deps.storage, &info.sender, netuid)?;
+    ensure!(
+        ensure_root(deps.storage, &info.sender).is_ok()
+            || get_registration_mode(deps.storage, netuid) == RegistrationMode::Permissioned,
+        ContractError::RegistrationModeDisabled {}
+    );
+
     ensure!(
         !UIDS.has(deps.storage, (netuid, &hotkey)),
         ContractError::AlreadyRegistered {}
     );
 
+    // The registrar names the coldkey, so an unowned hotkey must have approved it, root included.
+    ensure_hotkey_owner_approved(deps.storage, &coldkey, &hotkey)?;
     create_account_if_non_existent(deps.storage, &coldkey, &hotkey);
     ensure!(
         coldkey_owns_hotkey(deps.storage, &coldkey, &hotkey),
@@ -67,7 +93,9 @@ pub fn do_sudo_registration(
     let subnetwork_uid: u16;
     let current_block_number: u64 = env.block.height;
     let current_subnetwork_n: u16 = get_subnetwork_n(deps.storage, netuid);
-    if current_subnetwork_n < get_max_allowed_uids(deps.storage, netuid) {
+    if current_subnetwork_n < get_max_allowed_uids(deps.storage, netuid)
+        && get_vacant_uid(deps.storage, netuid).is_none()
+    {
         // --- 12.1.1 No replacement required, the uid appends the subnetwork.
         // We increment the subnetwork count here but not below.
         subnetwork_uid = current_subnetwork_n;
@@ -87,7 +115,7 @@ pub fn do_sudo_registration(
         subnetwork_uid = get_neuron_to_prune(deps.storage, deps.api, netuid, env.block.height);
 
         // --- 12.1.1 Replace the neuron account with the new info.
-        let _msgs = replace_neuron(
+        replace_neuron(
             deps.storage,
             deps.api,
             netuid,
@@ -170,6 +198,14 @@ pub fn do_burned_registration(
         if_subnet_allows_registration(deps.storage, netuid),
         ContractError::RegistrationDisabled {}
     );
+    ensure!(
+        matches!(
+            get_registration_mode(deps.storage, netuid),
+            RegistrationMode::Burn | RegistrationMode::Both
+        ),
+        ContractError::RegistrationModeDisabled {}
+    );
+    ensure_registration_allowlisted(deps.storage, netuid, &hotkey, Some(&coldkey))?;
 
     // --- 4. Ensure we are not exceeding the max allowed registrations per block.
     ensure!(
@@ -200,10 +236,14 @@ pub fn do_burned_registration(
         ContractError::NotEnoughTokens {}
     );
 
+    // --- 8. Ensure the hotkey approved the coldkey as its owner.
+    ensure_hotkey_owner_approved(deps.storage, &coldkey, &hotkey)?;
+
     // The burn occurs here.
     // same as below
     let burn_amount = get_burn_as_u64(deps.storage, netuid);
     burn_tokens(deps.storage, burn_amount)?;
+    let fee_msg = registration_fee_msg(deps.storage, netuid, burn_amount)?;
 
     // --- 9. If the network account does not exist we will create it here.
     create_account_if_non_existent(deps.storage, &coldkey, &hotkey);
@@ -224,7 +264,9 @@ pub fn do_burned_registration(
         ContractError::NetworkDoesNotExist {}
     );
 
-    if current_subnetwork_n < get_max_allowed_uids(deps.storage, netuid) {
+    if current_subnetwork_n < get_max_allowed_uids(deps.storage, netuid)
+        && get_vacant_uid(deps.storage, netuid).is_none()
+    {
         // --- 12.1.1 No replacement required, the uid appends the subnetwork.
         // We increment the subnetwork count here but not below.
         subnetwork_uid = current_subnetwork_n;
@@ -244,7 +286,7 @@ pub fn do_burned_registration(
         subnetwork_uid = get_neuron_to_prune(deps.storage, deps.api, netuid, env.block.height);
 
         // --- 13.1.1 Replace the neuron account with the new info.
-        let _msgs = replace_neuron(
+        replace_neuron(
             deps.storage,
             deps.api,
             netuid,
@@ -285,11 +327,185 @@ pub fn do_burned_registration(
         hotkey.clone()
     ));
 
-    // --- 16. Ok and done.
+    // --- 16. Return the overpaid tokens and done.
+    let refund = amount.u128() - burn_amount as u128;
+    let mut response = Response::default()
+        .add_messages(fee_msg)
+        .add_attribute("action", "neuron_registered")
+        .add_attribute("subnetwork_uid", format!("{}", subnetwork_uid))
+        .add_attribute("hotkey", hotkey)
+        .add_attribute("refunded", format!("{}", refund));
+    if refund > 0 {
+        response = response.add_message(BankMsg::Send {
+            to_address: coldkey.to_string(),
+            amount: coins(refund, denom),
+        });
+    }
+    Ok(response)
+}
+
+// ---- The implementation for the extrinsic do_staked_registration: registers the hotkey by
+// staking the paid tokens on it instead of burning them. The registration stake stays locked
+// for the immunity period, so the registrant gets it back when leaving the subnet later.
+//
+// # Raises:
+// 	* 'RegistrationModeDisabled':
+// 		- Thrown if the subnet is not in the stake registration mode.
+//
+// 	* 'NotEnoughTokens':
+// 		- Thrown if the paid tokens are below the subnet registration stake.
+//
+pub fn do_staked_registration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    hotkey_address: String,
+) -> Result<Response, ContractError> {
+    let denom = DENOM.load(deps.storage)?;
+    let amount = must_pay(&info, &denom).map_err(|_| ContractError::CouldNotConvertToBalance {})?;
+
+    // --- 1. Check that the caller has signed the transaction. (the coldkey of the pairing)
+    let coldkey = info.sender;
+    let hotkey = deps.api.addr_validate(&hotkey_address)?;
+
+    deps.api.debug(&format!(
+        "👾 do_staked_registration ( netuid:{:?} hotkey:{:?}, coldkey:{:?} )",
+        netuid, hotkey, coldkey
+    ));
+
+    // --- 2. Ensure the passed network is valid.
+    ensure!(
+        netuid != get_root_netuid(),
+        ContractError::OperationNotPermittedOnRootSubnet {}
+    );
+    ensure!(
+        if_subnet_exist(deps.storage, netuid),
+        ContractError::NetworkDoesNotExist {}
+    );
+
+    // --- 3. Ensure the passed network allows stake registrations.
+    ensure!(
+        if_subnet_allows_registration(deps.storage, netuid),
+        ContractError::RegistrationDisabled {}
+    );
+    ensure!(
+        get_registration_mode(deps.storage, netuid) == RegistrationMode::Stake,
+        ContractError::RegistrationModeDisabled {}
+    );
+    ensure_registration_allowlisted(deps.storage, netuid, &hotkey, Some(&coldkey))?;
+
+    // --- 4. Ensure we are not exceeding the max allowed registrations per block.
+    ensure!(
+        get_registrations_this_block(deps.storage, netuid)
+            < get_max_registrations_per_block(deps.storage, netuid),
+        ContractError::TooManyRegistrationsThisBlock {}
+    );
+
+    // --- 5. Ensure we are not exceeding the max allowed registrations per interval.
+    ensure!(
+        get_registrations_this_interval(deps.storage, netuid)
+            < get_target_registrations_per_interval(deps.storage, netuid) * 3,
+        ContractError::TooManyRegistrationsThisInterval {}
+    );
+
+    // --- 6. Ensure that the key is not already registered.
+    ensure!(
+        !UIDS.has(deps.storage, (netuid, &hotkey)),
+        ContractError::AlreadyRegistered {}
+    );
+
+    // --- 7. Ensure the caller pays at least the registration stake.
+    let current_block_number: u64 = env.block.height;
+    let stake = amount.u128() as u64;
+    ensure!(
+        stake >= get_registration_stake(deps.storage, netuid),
+        ContractError::NotEnoughTokens {}
+    );
+
+    // --- 8. Ensure the hotkey approved the coldkey as its owner.
+    ensure_hotkey_owner_approved(deps.storage, &coldkey, &hotkey)?;
+
+    // --- 9. If the network account does not exist we will create it here.
+    create_account_if_non_existent(deps.storage, &coldkey, &hotkey);
+
+    // --- 10. Ensure that the pairing is correct.
+    ensure!(
+        coldkey_owns_hotkey(deps.storage, &coldkey, &hotkey),
+        ContractError::NonAssociatedColdKey {}
+    );
+
+    // --- 11. Append neuron or prune it.
+    let subnetwork_uid: u16;
+    let current_subnetwork_n: u16 = get_subnetwork_n(deps.storage, netuid);
+
+    // Possibly there is no neuron slots at all.
+    ensure!(
+        get_max_allowed_uids(deps.storage, netuid) != 0,
+        ContractError::NetworkDoesNotExist {}
+    );
+
+    if current_subnetwork_n < get_max_allowed_uids(deps.storage, netuid)
+        && get_vacant_uid(deps.storage, netuid).is_none()
+    {
+        // --- 11.1 No replacement required, the uid appends the subnetwork.
+        subnetwork_uid = current_subnetwork_n;
+        append_neuron(
+            deps.storage,
+            deps.api,
+            netuid,
+            &hotkey,
+            current_block_number,
+        )?;
+        deps.api.debug("👾 add new neuron account");
+    } else {
+        // --- 11.2 Replacement required, we take the neuron with the lowest pruning score.
+        subnetwork_uid = get_neuron_to_prune(deps.storage, deps.api, netuid, env.block.height);
+        replace_neuron(
+            deps.storage,
+            deps.api,
+            netuid,
+            subnetwork_uid,
+            &hotkey,
+            current_block_number,
+        )?;
+        deps.api.debug("👾 prune neuron");
+    }
+
+    // --- 12. Stake the paid tokens on the hotkey and lock them for the immunity period.
+    let unlock_block =
+        current_block_number.saturating_add(get_immunity_period(deps.storage, netuid) as u64);
+    increase_stake_on_coldkey_hotkey_account(deps.storage, &coldkey, &hotkey, stake);
+    add_stake_lock(
+        deps.storage,
+        &coldkey,
+        &hotkey,
+        stake,
+        unlock_block,
+        current_block_number,
+    )?;
+
+    // --- 13. Increment block and interval counters.
+    REGISTRATIONS_THIS_INTERVAL.update(deps.storage, netuid, |val| -> StdResult<_> {
+        Ok(val.unwrap_or_default().saturating_add(1))
+    })?;
+    REGISTRATIONS_THIS_BLOCK.update(deps.storage, netuid, |val| -> StdResult<_> {
+        Ok(val.unwrap_or_default().saturating_add(1))
+    })?;
+
+    // --- 14. Deposit successful event.
+    deps.api.debug(&format!(
+        "👾 Neuron Registration Staked ( netuid:{:?} uid:{:?} hotkey:{:?} stake:{:?} ) ",
+        netuid, subnetwork_uid, hotkey, stake
+    ));
+
+    // --- 15. Ok and done.
     Ok(Response::default()
         .add_attribute("action", "neuron_registered")
         .add_attribute("subnetwork_uid", format!("{}", subnetwork_uid))
-        .add_attribute("hotkey", hotkey))
+        .add_attribute("hotkey", hotkey)
+        .add_attribute("stake", format!("{}", stake))
+        .add_attribute("unlock_block", format!("{}", unlock_block)))
 }
 
 // ---- The implementation for the extrinsic do_registration.
@@ -382,6 +598,15 @@ pub fn do_registration(
         if_subnet_allows_registration(deps.storage, netuid),
         ContractError::RegistrationDisabled {}
     );
+    ensure!(
+        matches!(
+            get_registration_mode(deps.storage, netuid),
+            RegistrationMode::Pow | RegistrationMode::Both
+        ),
+        ContractError::RegistrationModeDisabled {}
+    );
+    // Only the hotkey signs the work, a listed coldkey named by it doesn't count.
+    ensure_registration_allowlisted(deps.storage, netuid, &hotkey, None)?;
 
     // --- 4. Ensure we are not exceeding the max allowed registrations per block.
     // TODO TESTS FAIL HERE
@@ -412,7 +637,7 @@ pub fn do_registration(
         ContractError::InvalidWorkBlock {}
     );
     ensure!(
-        current_block_number - block_number < 3,
+        current_block_number - block_number < WORK_BLOCKS_WINDOW,
         ContractError::InvalidWorkBlock {}
     );
 
@@ -425,7 +650,7 @@ pub fn do_registration(
     ); // Check that the work meets difficulty.
 
     // --- 7. Check Work is the product of the nonce, the block number, and hotkey. Add this as used work.
-    let seal: H256 = create_seal_hash(block_number, nonce, hotkey.as_str());
+    let seal: H256 = create_seal_hash(deps.storage, block_number, nonce, hotkey.as_str())?;
     // TODO revisit PoW cross testing
     ensure!(seal == work_hash, ContractError::InvalidSeal {});
     USED_WORK.save(deps.storage, work.clone(), &current_block_number)?;
@@ -449,7 +674,9 @@ pub fn do_registration(
         ContractError::NetworkDoesNotExist {}
     );
 
-    if current_subnetwork_n < get_max_allowed_uids(deps.storage, netuid) {
+    if current_subnetwork_n < get_max_allowed_uids(deps.storage, netuid)
+        && get_vacant_uid(deps.storage, netuid).is_none()
+    {
         // --- 11.1.1 No replacement required, the uid appends the subnetwork.
         // We increment the subnetwork count here but not below.
         subnetwork_uid = current_subnetwork_n;
@@ -469,7 +696,7 @@ pub fn do_registration(
         subnetwork_uid = get_neuron_to_prune(deps.storage, deps.api, netuid, current_block_number);
 
         // --- 11.1.1 Replace the neuron account with the new info.
-        let _msgs = replace_neuron(
+        replace_neuron(
             deps.storage,
             deps.api,
             netuid,
@@ -513,7 +740,149 @@ pub fn do_registration(
         .add_attribute("hotkey", hotkey))
 }
 
-#[cfg(feature = "pow-faucet")]
+// Checks that the hotkey or the coldkey is in the subnet registration allowlist when it is enabled.
+// The coldkey is passed only when it signed the registration.
+//
+fn ensure_registration_allowlisted(
+    store: &dyn Storage,
+    netuid: u16,
+    hotkey: &Addr,
+    coldkey: Option<&Addr>,
+) -> Result<(), ContractError> {
+    if !is_registration_allowlist_enabled(store, netuid) {
+        return Ok(());
+    }
+    ensure!(
+        REGISTRATION_ALLOWLIST.has(store, (netuid, hotkey))
+            || coldkey.is_some_and(|coldkey| REGISTRATION_ALLOWLIST.has(store, (netuid, coldkey))),
+        ContractError::NotInRegistrationAllowlist {}
+    );
+    Ok(())
+}
+
+// Returns the message moving the burned registration fee to the subnet fee destination.
+// Recycled fees stay in the contract balance.
+//
+pub fn registration_fee_msg(
+    store: &dyn Storage,
+    netuid: u16,
+    amount: u64,
+) -> Result<Option<BankMsg>, ContractError> {
+    if amount == 0 {
+        return Ok(None);
+    }
+    let amount = coins(amount as u128, DENOM.load(store)?);
+    let msg = match get_registration_fee_destination(store, netuid) {
+        RegistrationFeeDestination::Burn => Some(BankMsg::Burn { amount }),
+        RegistrationFeeDestination::Recycle => None,
+        RegistrationFeeDestination::SubnetOwner => Some(BankMsg::Send {
+            to_address: SUBNET_OWNER.load(store, netuid)?.to_string(),
+            amount,
+        }),
+    };
+    Ok(msg)
+}
+
+// ---- The implementation for the extrinsic deregister: frees the uid slot of the hotkey on the network.
+// The slot is filled by the next registrant ahead of pruning.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the hotkey or of its owning coldkey.
+//
+// 	* 'netuid' (u16):
+// 		- The u16 network identifier.
+//
+// 	* 'hotkey' (Option<T::AccountId>):
+// 		- The hotkey to deregister, the caller itself when not passed.
+//
+// # Raises:
+// 	* 'NotRegistered':
+// 		- The hotkey is not registered on the network.
+//
+// 	* 'NonAssociatedColdKey':
+// 		- The caller is neither the hotkey nor its owning coldkey.
+//
+pub fn do_deregister(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    hotkey_address: Option<String>,
+) -> Result<Response, ContractError> {
+    // --- 1. Check that the caller is the hotkey or its owning coldkey.
+    let hotkey = match hotkey_address {
+        Some(hotkey_address) => deps.api.addr_validate(&hotkey_address)?,
+        None => info.sender.clone(),
+    };
+
+    deps.api.debug(&format!(
+        "👾 do_deregister ( origin:{:?} netuid:{:?} hotkey:{:?} )",
+        info.sender, netuid, hotkey
+    ));
+
+    // --- 2. Ensure the passed network is valid.
+    ensure!(
+        netuid != get_root_netuid(),
+        ContractError::OperationNotPermittedOnRootSubnet {}
+    );
+    ensure!(
+        if_subnet_exist(deps.storage, netuid),
+        ContractError::NetworkDoesNotExist {}
+    );
+
+    // --- 3. Ensure the hotkey is registered and the caller controls it.
+    let uid = get_uid_for_net_and_hotkey(deps.storage, netuid, &hotkey)?;
+    let owner = get_owning_coldkey_for_hotkey(deps.storage, &hotkey);
+    ensure!(
+        info.sender == hotkey || owner == info.sender,
+        ContractError::NonAssociatedColdKey {}
+    );
+
+    // --- 3a. Ensure the owner stake is not locked, e.g. by a stake registration in immunity.
+    ensure!(
+        get_locked_stake(deps.storage, &owner, &hotkey, env.block.height) == 0,
+        ContractError::StakeLocked {}
+    );
+
+    // --- 4. Clear the neuron state of the slot.
+    WEIGHTS.remove(deps.storage, (netuid, uid));
+    BONDS.remove(deps.storage, (netuid, uid));
+    AXONS.remove(deps.storage, (netuid, &hotkey));
+    PROMETHEUS.remove(deps.storage, (netuid, &hotkey));
+    set_active_for_uid(deps.storage, netuid, uid, false);
+    set_validator_permit_for_uid(deps.storage, netuid, uid, false);
+    set_pruning_score_for_uid(deps.storage, deps.api, netuid, uid, 0);
+
+    // --- 5. Free the slot.
+    remove_neuron(deps.storage, netuid, uid)?;
+
+    deps.api.debug(&format!(
+        "👾 NeuronDeregistered( netuid:{:?} uid:{:?} hotkey:{:?} ) ",
+        netuid, uid, hotkey
+    ));
+
+    // --- 6. Ok and done.
+    Ok(Response::default()
+        .add_attribute("action", "neuron_deregistered")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("uid", format!("{}", uid))
+        .add_attribute("hotkey", hotkey))
+}
+
+// ---- The implementation for the extrinsic do_faucet: pays out tokens from the root funded faucet
+// reserve to the coldkey which solved the faucet PoW, at most once per cooldown.
+//
+// # Raises:
+// 	* 'FaucetDisabled':
+// 		- Thrown if the faucet is not allowed on the verse.
+//
+// 	* 'FaucetCooldown':
+// 		- Thrown if the coldkey got a payout within the cooldown.
+//
+// 	* 'FaucetReserveDepleted':
+// 		- Thrown if the reserve can't cover the payout.
+//
 pub fn do_faucet(
     deps: DepsMut,
     env: Env,
@@ -531,53 +900,71 @@ pub fn do_faucet(
     // --- 1. Check that the caller has signed the transaction.
     let coldkey = info.sender;
     deps.api
-        .debug(&format!("do_faucet( coldkey:{:?} )", coldkey));
+        .debug(&format!("👾 do_faucet ( coldkey:{:?} )", coldkey));
 
-    // --- 2. Ensure the passed block number is valid, not in the future or too old.
-    // Work must have been done within 3 blocks (stops long range attacks).
+    // --- 2. Ensure the coldkey is out of the cooldown.
+    let config = get_faucet_config(deps.storage);
     let current_block_number: u64 = env.block.height;
+    if let Some(last_payout) = FAUCET_LAST_PAYOUT.may_load(deps.storage, &coldkey)? {
+        ensure!(
+            current_block_number >= last_payout.saturating_add(config.cooldown),
+            ContractError::FaucetCooldown {}
+        );
+    }
+
+    // --- 3. Ensure the passed block number is valid, not in the future or too old.
+    // Work must have been done within 3 blocks (stops long range attacks).
     ensure!(
-        block_number <= env.block.height,
+        block_number <= current_block_number,
         ContractError::InvalidWorkBlock {}
     );
     ensure!(
-        env.block.height - block_number < 3,
+        current_block_number - block_number < WORK_BLOCKS_WINDOW,
         ContractError::InvalidWorkBlock {}
     );
 
-    // --- 3. Ensure the supplied work passes the difficulty.
-    // let difficulty = U256::from(1_000_000); // Base faucet difficulty.
-    // let work_hash: H256 = vec_to_hash(work.clone());
-    // ensure!(
-    //     hash_meets_difficulty(&work_hash, difficulty),
-    //     ContractError::InvalidDifficulty {}
-    // ); // Check that the work meets difficulty.
-
-    // --- 4. Check Work is the product of the nonce, the block number, and hotkey. Add this as used work.
-    // let seal: H256 = create_seal_hash(block_number, nonce, coldkey.as_str());
-    // ensure!(seal == work_hash, ContractError::InvalidSeal {});
-    // USED_WORK.save(deps.storage, work.clone(), &current_block_number)?;
-
-    // --- 5. Add Balance via faucet.
-    let balance_to_add: u64 = 100_000_000_000;
-    let balance_to_be_added_as_balance = u64_to_balance(balance_to_add);
-    add_balance_to_coldkey_account(&coldkey, balance_to_be_added_as_balance.unwrap());
-    TOTAL_ISSUANCE.update(deps.storage, |total_issuance| -> StdResult<_> {
-        Ok(total_issuance.saturating_add(balance_to_add))
-    })?;
+    // --- 4. Ensure the supplied work passes the difficulty and was not used before.
+    ensure!(work.len() == 32, ContractError::InvalidSeal {});
+    ensure!(
+        !USED_WORK.has(deps.storage, work.clone()),
+        ContractError::WorkRepeated {}
+    );
+    let work_hash: H256 = vec_to_hash(work.clone());
+    ensure!(
+        hash_meets_difficulty(&work_hash, U256::from(config.difficulty)),
+        ContractError::InvalidDifficulty {}
+    );
+
+    // --- 5. Check Work is the product of the nonce, the block number, and coldkey. Add this as used work.
+    let seal: H256 = create_seal_hash(deps.storage, block_number, nonce, coldkey.as_str())?;
+    ensure!(seal == work_hash, ContractError::InvalidSeal {});
+    USED_WORK.save(deps.storage, work, &current_block_number)?;
 
-    // --- 6. Deposit successful event.
+    // --- 6. Pay out from the reserve.
+    let reserve = get_faucet_reserve(deps.storage);
+    ensure!(
+        reserve >= config.amount,
+        ContractError::FaucetReserveDepleted {}
+    );
+    FAUCET_RESERVE.save(deps.storage, &(reserve - config.amount))?;
+    FAUCET_LAST_PAYOUT.save(deps.storage, &coldkey, &current_block_number)?;
+
+    // --- 7. Deposit successful event.
     deps.api.debug(&format!(
-        "Faucet( coldkey:{:?} amount:{:?} ) ",
-        coldkey, balance_to_add
+        "👾 Faucet ( coldkey:{:?} amount:{:?} ) ",
+        coldkey, config.amount
     ));
-    // TODO Create send tokens msgs and add to response
 
-    // --- 7. Ok and done.
+    // --- 8. Ok and done.
+    let denom = DENOM.load(deps.storage)?;
     Ok(Response::default()
+        .add_message(BankMsg::Send {
+            to_address: coldkey.to_string(),
+            amount: coins(config.amount as u128, denom),
+        })
         .add_attribute("action", "faucet")
         .add_attribute("coldkey", coldkey)
-        .add_attribute("amount", format!("{}", balance_to_add)))
+        .add_attribute("amount", format!("{}", config.amount)))
 }
 
 pub fn vec_to_hash(vec_hash: Vec<u8>) -> H256 {
@@ -596,13 +983,27 @@ pub fn get_neuron_to_prune(
     netuid: u16,
     current_block: u64,
 ) -> u16 {
+    if get_subnetwork_n(store, netuid) == 0 {
+        return 0;
+    } // If there are no neurons in this network.
+    if let Some(vacant_uid) = get_vacant_uid(store, netuid) {
+        return vacant_uid;
+    } // Slots left by deregistered neurons go first.
+    let uid_to_prune = get_uid_with_min_pruning_score(store, netuid, current_block);
+    // We replace the pruning score here with u16 max to ensure that all peers always have a
+    // pruning score. In the event that every peer has been pruned this function will prune
+    // the last element in the network continually.
+    set_pruning_score_for_uid(store, api, netuid, uid_to_prune, u16::MAX);
+    uid_to_prune
+}
+
+// Returns the uid with the lowest pruning score out of immunity period, or the uid with the lowest
+// pruning score in immunity period if there is none. Doesn't account for vacant uids.
+pub fn get_uid_with_min_pruning_score(store: &dyn Storage, netuid: u16, current_block: u64) -> u16 {
     let mut min_score: u16 = u16::MAX;
     let mut min_score_in_immunity_period = u16::MAX;
     let mut uid_with_min_score = 0;
     let mut uid_with_min_score_in_immunity_period: u16 = 0;
-    if get_subnetwork_n(store, netuid) == 0 {
-        return 0;
-    } // If there are no neurons in this network.
     for neuron_uid_i in 0..get_subnetwork_n(store, netuid) {
         let pruning_score: u16 = get_pruning_score_for_uid(store, netuid, neuron_uid_i);
         let block_at_registration: u64 =
@@ -637,20 +1038,9 @@ pub fn get_neuron_to_prune(
     }
     if min_score == u16::MAX {
         //all neuorns are in immunity period
-        set_pruning_score_for_uid(
-            store,
-            api,
-            netuid,
-            uid_with_min_score_in_immunity_period,
-            u16::MAX,
-        );
-        return uid_with_min_score_in_immunity_period;
+        uid_with_min_score_in_immunity_period
     } else {
-        // We replace the pruning score here with u16 max to ensure that all peers always have a
-        // pruning score. In the event that every peer has been pruned this function will prune
-        // the last element in the network continually.
-        set_pruning_score_for_uid(store, api, netuid, uid_with_min_score, u16::MAX);
-        return uid_with_min_score;
+        uid_with_min_score
     }
 }
 
@@ -676,33 +1066,84 @@ pub fn hash_meets_difficulty(hash: &H256, difficulty: U256) -> bool {
     !overflowed
 }
 
-pub fn get_block_hash_from_u64(_: u64) -> H256 {
-    // TODO cosmwasm don't have api to access block hash, not possible to access hash because hash is result of execution
-    // let block_number: T::BlockNumber = TryInto::<T::BlockNumber>::try_into(block_number)
-    //     .ok()
-    //     .expect("convert u64 to block number.");
-    // let block_hash_at_number: <T as frame_system::Config>::Hash =
-    //     system::Pallet::<T>::block_hash(block_number);
-    // let vec_hash: Vec<u8> = block_hash_at_number.as_ref().into_iter().cloned().collect();
-    // let deref_vec_hash: &[u8] = &vec_hash; // c: &[u8]
-    // let real_hash: H256 = H256::from_slice(deref_vec_hash);
+// Returns the seed recorded by the block step of the block, None for unknown or expired blocks.
+//
+pub fn get_block_hash_from_u64(store: &dyn Storage, block_number: u64) -> Option<H256> {
+    BLOCK_SEEDS
+        .may_load(store, block_number)
+        .unwrap()
+        .map(|seed| H256::from_slice(&seed))
+}
 
-    // log::trace!(
-    //     target: LOG_TARGET,
-    //     "block_number: {:?}, vec_hash: {:?}, real_hash: {:?}",
-    //     block_number,
-    //     vec_hash,
-    //     real_hash
-    // );
-    let real_hash = H256::zero();
+// Records the seed of the current block which PoW registration work commits to. The seed chains
+// the previous seed with the block data so work can't be precomputed before the block.
+// Seeds older than the work window are dropped.
+//
+pub fn record_block_seed(store: &mut dyn Storage, env: &Env) -> Result<(), ContractError> {
+    let current_block = env.block.height;
+    if BLOCK_SEEDS.has(store, current_block) {
+        return Ok(());
+    }
+    let previous_seed: Vec<u8> = BLOCK_SEEDS
+        .range(store, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .map_or(vec![0u8; 32], |(_, seed)| seed);
+
+    let mut seed_bytes: Vec<u8> = previous_seed;
+    seed_bytes.extend_from_slice(&current_block.to_le_bytes());
+    seed_bytes.extend_from_slice(&env.block.time.nanos().to_le_bytes());
+    seed_bytes.extend_from_slice(env.block.chain_id.as_bytes());
+    BLOCK_SEEDS.save(store, current_block, &keccak_256(&seed_bytes).to_vec())?;
+
+    let expired: Vec<u64> = BLOCK_SEEDS
+        .keys(
+            store,
+            None,
+            Some(Bound::inclusive(current_block.saturating_sub(WORK_BLOCKS_WINDOW))),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<u64>>>()?;
+    for block in expired {
+        BLOCK_SEEDS.remove(store, block);
+    }
 
-    return real_hash;
+    Ok(())
+}
+
+// Removes used work which can't be submitted again because its block is out of the work window.
+// Checks a bounded number of entries per call, continuing from where the previous call stopped.
+// Returns the number of removed entries.
+//
+pub fn prune_used_work(store: &mut dyn Storage, current_block: u64) -> Result<u64, ContractError> {
+    let cursor = USED_WORK_PRUNE_CURSOR.may_load(store)?;
+    let scanned: Vec<(Vec<u8>, u64)> = USED_WORK
+        .range(store, cursor.map(Bound::exclusive), None, Order::Ascending)
+        .take(USED_WORK_PRUNE_LIMIT)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // --- Restart from the first entry once the end of the set is reached.
+    match scanned.last() {
+        Some((work, _)) if scanned.len() == USED_WORK_PRUNE_LIMIT => {
+            USED_WORK_PRUNE_CURSOR.save(store, work)?
+        }
+        _ => USED_WORK_PRUNE_CURSOR.remove(store),
+    }
+
+    let mut pruned: u64 = 0;
+    for (work, used_at) in scanned {
+        if used_at.saturating_add(WORK_BLOCKS_WINDOW) <= current_block {
+            USED_WORK.remove(store, work);
+            pruned += 1;
+        }
+    }
+
+    Ok(pruned)
 }
 
-#[cfg(test)]
 pub fn hash_to_vec(hash: H256) -> Vec<u8> {
     let hash_as_bytes: &[u8] = hash.as_bytes();
-    let hash_as_vec: Vec<u8> = hash_as_bytes.iter().cloned().collect();
+    let hash_as_vec: Vec<u8> = hash_as_bytes.to_vec();
     return hash_as_vec;
 }
 
@@ -787,9 +1228,15 @@ pub fn hash_block_and_hotkey(block_hash_bytes: &[u8], hotkey: &str) -> H256 {
     return seal_hash;
 }
 
-pub fn create_seal_hash(block_number_u64: u64, nonce_u64: u64, hotkey: &str) -> H256 {
+pub fn create_seal_hash(
+    store: &dyn Storage,
+    block_number_u64: u64,
+    nonce_u64: u64,
+    hotkey: &str,
+) -> Result<H256, ContractError> {
     let nonce = U256::from(nonce_u64);
-    let block_hash_at_number: H256 = get_block_hash_from_u64(block_number_u64);
+    let block_hash_at_number: H256 =
+        get_block_hash_from_u64(store, block_number_u64).ok_or(ContractError::InvalidWorkBlock {})?;
     let block_hash_bytes: &[u8] = block_hash_at_number.as_bytes();
     let binding = hash_block_and_hotkey(block_hash_bytes, hotkey);
     let block_and_hotkey_hash_bytes: &[u8] = binding.as_bytes();
@@ -852,7 +1299,7 @@ pub fn create_seal_hash(block_number_u64: u64, nonce_u64: u64, hotkey: &str) ->
     //     seal_hash
     // );
 
-    return seal_hash;
+    Ok(seal_hash)
 }
 
 pub fn get_difficulty(store: &dyn Storage, netuid: u16) -> U256 {
@@ -871,10 +1318,10 @@ pub fn create_work_for_block_number(
 ) -> (u64, Vec<u8>) {
     let difficulty = get_difficulty(store, netuid);
     let mut nonce: u64 = start_nonce;
-    let mut work: H256 = create_seal_hash(block_number, nonce, hotkey);
+    let mut work: H256 = create_seal_hash(store, block_number, nonce, hotkey).unwrap();
     while !hash_meets_difficulty(&work, difficulty) {
         nonce = nonce + 1;
-        work = create_seal_hash(block_number, nonce, hotkey);
+        work = create_seal_hash(store, block_number, nonce, hotkey).unwrap();
     }
     let vec_work: Vec<u8> = hash_to_vec(work);
     return (nonce, vec_work);
@@ -1,5 +1,5 @@
 use cosmwasm_std::StdError::GenericErr;
-use cosmwasm_std::{ensure, Addr, Api, DepsMut, Env, MessageInfo, Order, StdResult, Storage};
+use cosmwasm_std::{coins, ensure, Addr, Api, BankMsg, DepsMut, Env, MessageInfo, Order, StdResult, Storage};
 use cw_utils::must_pay;
 use cyber_std::Response;
 use substrate_fixed::types::I64F64;
@@ -8,26 +8,32 @@ use crate::block_step::blocks_until_next_epoch;
 use crate::epoch::get_float_kappa;
 use crate::math::{inplace_normalize_64, matmul_64, vec_fixed64_to_u64};
 use crate::staking::{
-    create_account_if_non_existent, delegate_hotkey, get_total_stake_for_hotkey, hotkey_is_delegate,
+    add_unissued_claimable_balance, create_account_if_non_existent, delegate_hotkey, ensure_hotkey_owner_approved, get_boosted_stake_for_hotkey, get_total_stake_for_hotkey, hotkey_is_delegate,
 };
 use crate::state::{
     Metadata, ACTIVE, ACTIVITY_CUTOFF, ADJUSTMENTS_ALPHA, ADJUSTMENT_INTERVAL, BLOCKS_SINCE_LAST_STEP, BONDS,
     BONDS_MOVING_AVERAGE, BURN, BURN_REGISTRATIONS_THIS_INTERVAL, CONSENSUS, DENOM, DIFFICULTY,
     DIVIDENDS, EMISSION, EMISSION_VALUES, IMMUNITY_PERIOD, INCENTIVE, KAPPA, KEYS,
-    LAST_ADJUSTMENT_BLOCK, LAST_UPDATE, MAX_ALLOWED_UIDS, MAX_ALLOWED_VALIDATORS, MAX_BURN,
+    LAST_ADJUSTMENT_BLOCK, LAST_UPDATE, LOW_TRUST_EPOCHS, MAX_ALLOWED_UIDS, MAX_ALLOWED_VALIDATORS, MAX_BURN,
     MAX_DIFFICULTY, MAX_REGISTRATION_PER_BLOCK, MAX_WEIGHTS_LIMIT, NETWORKS_METADATA, MIN_ALLOWED_WEIGHTS,
     MIN_BURN, MIN_DIFFICULTY, NETWORKS_ADDED, NETWORK_IMMUNITY_PERIOD, NETWORK_LAST_LOCK_COST,
     NETWORK_LAST_REGISTERED, NETWORK_LOCK_REDUCTION_INTERVAL, NETWORK_MIN_LOCK_COST,
     NETWORK_MODALITY, NETWORK_RATE_LIMIT, NETWORK_REGISTERED_AT, NETWORK_REGISTRATION_ALLOWED,
     PENDING_EMISSION, POW_REGISTRATIONS_THIS_INTERVAL, PRUNING_SCORES, RANK,
-    RAO_RECYCLED_FOR_REGISTRATION, REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, RHO,
-    SERVING_RATE_LIMIT, SUBNETWORK_N, SUBNET_LIMIT, SUBNET_OWNER,
+    RAO_RECYCLED_FOR_REGISTRATION, REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL,
+    REGISTRATION_ALLOWLIST, REGISTRATION_ALLOWLIST_ENABLED, REGISTRATION_FEE_DESTINATION,
+    REGISTRATION_MODE, REGISTRATION_STAKE, RHO,
+    SERVING_ALLOW_PRIVATE_IPS, SERVING_RATE_LIMIT, SLASHING, SUBNETWORK_N, SUBNET_LIMIT, SUBNET_LOCKED, SUBNET_OWNER,
     TARGET_REGISTRATIONS_PER_INTERVAL, TEMPO, TOTAL_NETWORKS, TRUST, UIDS, VALIDATOR_PERMIT,
     VALIDATOR_TRUST, WEIGHTS, WEIGHTS_SET_RATE_LIMIT, WEIGHTS_VERSION_KEY, TOTAL_REWARDS,
 };
-use crate::uids::{append_neuron, get_hotkey_for_net_and_uid, get_subnetwork_n, replace_neuron};
+use crate::uids::{
+    append_neuron, get_hotkey_for_net_and_uid, get_subnetwork_n, get_vacant_uid, remove_neuron,
+    replace_neuron,
+};
 use crate::utils::{
-    get_block_emission, get_emission_value, get_max_allowed_uids, get_max_registrations_per_block,
+    get_block_emission, get_default_take, get_emission_value, get_max_allowed_uids,
+    get_max_registrations_per_block, get_root_auto_delegate, get_root_min_stake,
     get_registrations_this_block, get_registrations_this_interval, get_rho, get_subnet_owner,
     get_target_registrations_per_interval, get_tempo, set_subnet_locked_balance,
 };
@@ -287,6 +293,9 @@ pub fn root_epoch(
         }));
     }
 
+    // --- 0a. Prune root validators which fell below the min stake.
+    prune_root_validators_below_min_stake(store, api)?;
+
     // --- 1. Retrieves the number of root validators on subnets.
     let n: u16 = get_num_root_validators(store);
     api.debug(&format!("root_validators: {:?}", n));
@@ -333,9 +342,10 @@ pub fn root_epoch(
 
     // --- 6. Retrieves and stores the stake value associated with each hotkey on the root network.
     // Stakes are stored in a 64-bit fixed point representation for precise calculations.
+    // Locked stake counts with its bonus.
     let mut stake_i64: Vec<I64F64> = vec![I64F64::from_num(0.0); n as usize];
     for (uid_i, hotkey) in hotkeys.iter() {
-        stake_i64[*uid_i as usize] = I64F64::from_num(get_total_stake_for_hotkey(store, &hotkey));
+        stake_i64[*uid_i as usize] = I64F64::from_num(get_boosted_stake_for_hotkey(store, &hotkey));
     }
     inplace_normalize_64(&mut stake_i64);
     api.debug(&format!("🔵 stake: {:?}\n", &stake_i64));
@@ -427,6 +437,40 @@ pub fn root_epoch(
     Ok(())
 }
 
+// Removes the root validators with total stake below the root min stake and leaves their slots
+// vacant for the next root registrations. Returns the number of pruned validators.
+//
+pub fn prune_root_validators_below_min_stake(
+    store: &mut dyn Storage,
+    api: &dyn Api,
+) -> Result<u16, ContractError> {
+    let root_netuid: u16 = get_root_netuid();
+    let min_stake = get_root_min_stake(store);
+    if min_stake == 0 {
+        return Ok(0);
+    }
+
+    let below_min_stake: Vec<(u16, Addr)> = KEYS
+        .prefix(root_netuid)
+        .range(store, None, None, Order::Ascending)
+        .filter(|item| match item {
+            Ok((_, hotkey)) => get_total_stake_for_hotkey(store, hotkey) < min_stake,
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (uid, hotkey) in below_min_stake.iter() {
+        WEIGHTS.remove(store, (root_netuid, *uid));
+        remove_neuron(store, root_netuid, *uid)?;
+        api.debug(&format!(
+            "🔵 prune root validator: {:?} on uid {:?}",
+            hotkey, uid
+        ));
+    }
+
+    Ok(below_min_stake.len() as u16)
+}
+
 // Registers a user's hotkey to the root network.
 //
 // This function is responsible for registering the hotkey of a user.
@@ -483,9 +527,18 @@ pub fn do_root_register(
         ContractError::AlreadyRegistered {}
     );
 
+    // --- 5. Ensure the hotkey approved the coldkey as its owner.
+    ensure_hotkey_owner_approved(deps.storage, &coldkey, &hotkey)?;
+
     // --- 6. Create a network account for the user if it doesn't exist.
     create_account_if_non_existent(deps.storage, &coldkey, &hotkey);
 
+    // --- 6a. Ensure the hotkey has the min stake to join the root network.
+    ensure!(
+        get_total_stake_for_hotkey(deps.storage, &hotkey) >= get_root_min_stake(deps.storage),
+        ContractError::StakeTooLowForRoot {}
+    );
+
     // --- 7. Fetch the current size of the subnetwork.
     let current_num_root_validators: u16 = get_num_root_validators(deps.storage);
 
@@ -494,7 +547,22 @@ pub fn do_root_register(
 
     // --- 8. Check if the root net is below its allowed size.
     // max allowed is senate size.
-    if current_num_root_validators < get_max_root_validators(deps.storage) {
+    if let Some(vacant_uid) = get_vacant_uid(deps.storage, root_netuid) {
+        // --- 8.1 Take the slot of a pruned root validator.
+        subnetwork_uid = vacant_uid;
+        replace_neuron(
+            deps.storage,
+            deps.api,
+            root_netuid,
+            subnetwork_uid,
+            &hotkey,
+            current_block_number,
+        )?;
+        deps.api.debug(&format!(
+            "🔵 add new neuron: {:?} on vacant uid {:?}",
+            hotkey, subnetwork_uid
+        ));
+    } else if current_num_root_validators < get_max_root_validators(deps.storage) {
         // --- 12.1.1 We can append to the subnetwork as it's not full.
         subnetwork_uid = current_num_root_validators;
 
@@ -540,7 +608,7 @@ pub fn do_root_register(
 
         // --- 13.1.3 The new account has a higher stake than the one being replaced.
         // Replace the neuron account with new information.
-        let _msgs = replace_neuron(
+        replace_neuron(
             deps.storage,
             deps.api,
             root_netuid,
@@ -580,9 +648,10 @@ pub fn do_root_register(
     //     T::SenateMembers::add_member(&hotkey)?;
     // }
 
-    // --- 13. Force all members on root to become a delegate.
-    if !hotkey_is_delegate(deps.storage, &hotkey) {
-        delegate_hotkey(deps.storage, &hotkey, 13107);
+    // --- 13. Make members on root a delegate with the default take, unless disabled.
+    if get_root_auto_delegate(deps.storage) && !hotkey_is_delegate(deps.storage, &hotkey) {
+        let take = get_default_take(deps.storage);
+        delegate_hotkey(deps.storage, &hotkey, take);
     }
 
     // --- 14. Update the registration counters for both the block and interval.
@@ -703,10 +772,19 @@ pub fn user_add_network(
         netuid_to_register, 0
     ));
 
-    // --- 9. Return success.
-    Ok(Response::default()
+    // --- 9. Return the overpaid tokens and success.
+    let refund = amount.u128() - lock_amount as u128;
+    let mut response = Response::default()
         .add_attribute("active", "network_added")
-        .add_attribute("netuid_to_register", format!("{}", netuid_to_register)))
+        .add_attribute("netuid_to_register", format!("{}", netuid_to_register))
+        .add_attribute("refunded", format!("{}", refund));
+    if refund > 0 {
+        response = response.add_message(BankMsg::Send {
+            to_address: coldkey.to_string(),
+            amount: coins(refund, denom),
+        });
+    }
+    Ok(response)
 }
 
 // Facilitates the removal of a user's subnetwork.
@@ -855,9 +933,11 @@ pub fn init_new_network(
 // returns if any internal checks fail.
 //
 pub fn remove_network(store: &mut dyn Storage, netuid: u16) -> Result<(), ContractError> {
-    // --- 1. Return balance to subnet owner.
-    // let _owner_coldkey = get_subnet_owner(store, netuid);
-    // let _reserved_amount = get_subnet_locked_balance(store, netuid);
+    // --- 1. Return locked balance to subnet owner claimable balance.
+    // The locked tokens are already held by the contract, so no new issuance.
+    let owner_coldkey = get_subnet_owner(store, netuid);
+    let reserved_amount = SUBNET_LOCKED.may_load(store, netuid)?.unwrap_or_default();
+    add_unissued_claimable_balance(store, &owner_coldkey, reserved_amount);
 
     // --- 2. Remove network count.
     SUBNETWORK_N.remove(store, netuid);
@@ -896,6 +976,8 @@ pub fn remove_network(store: &mut dyn Storage, netuid: u16) -> Result<(), Contra
     LAST_UPDATE.remove(store, netuid);
     VALIDATOR_PERMIT.remove(store, netuid);
     VALIDATOR_TRUST.remove(store, netuid);
+    SLASHING.remove(store, netuid);
+    LOW_TRUST_EPOCHS.prefix(netuid).clear(store, None);
 
     // --- 10. Erase network parameters.
     TEMPO.remove(store, netuid);
@@ -911,8 +993,7 @@ pub fn remove_network(store: &mut dyn Storage, netuid: u16) -> Result<(), Contra
     POW_REGISTRATIONS_THIS_INTERVAL.remove(store, netuid);
     BURN_REGISTRATIONS_THIS_INTERVAL.remove(store, netuid);
 
-    // --- 11. Add the balance back to the owner.
-    // TODO create messages to send token here or burn them during creation, decide later
+    // --- 11. The locked balance was credited back to the owner.
     set_subnet_locked_balance(store, netuid, 0);
     SUBNET_OWNER.remove(store, netuid);
 
@@ -934,7 +1015,13 @@ pub fn remove_network(store: &mut dyn Storage, netuid: u16) -> Result<(), Contra
     KAPPA.remove(store, netuid);
     RHO.remove(store, netuid);
     RAO_RECYCLED_FOR_REGISTRATION.remove(store, netuid);
+    REGISTRATION_FEE_DESTINATION.remove(store, netuid);
+    REGISTRATION_MODE.remove(store, netuid);
+    REGISTRATION_STAKE.remove(store, netuid);
+    REGISTRATION_ALLOWLIST_ENABLED.remove(store, netuid);
+    REGISTRATION_ALLOWLIST.prefix(netuid).clear(store, None);
     SERVING_RATE_LIMIT.remove(store, netuid);
+    SERVING_ALLOW_PRIVATE_IPS.remove(store, netuid);
     MIN_DIFFICULTY.remove(store, netuid);
     MAX_DIFFICULTY.remove(store, netuid);
     ADJUSTMENTS_ALPHA.remove(store, netuid);
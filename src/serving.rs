@@ -1,8 +1,10 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
 use cosmwasm_std::{ensure, Addr, DepsMut, Env, MessageInfo, Storage, Uint128};
 
 use crate::state::{AxonInfo, AxonInfoOf, PrometheusInfo, PrometheusInfoOf, AXONS, PROMETHEUS};
 use crate::uids::is_hotkey_registered_on_any_network;
-use crate::utils::get_serving_rate_limit;
+use crate::utils::{get_serving_allow_private_ips, get_serving_rate_limit};
 use crate::ContractError;
 use cyber_std::Response;
 
@@ -78,10 +80,14 @@ pub fn do_serve_axon(
         ContractError::NotRegistered {}
     );
 
-    // --- 3. Check the ip signature validity.
+    // --- 3. Check the ip signature validity, private ips only on subnets which allow them.
     ensure!(is_valid_ip_type(ip_type), ContractError::InvalidIpType {});
     ensure!(
-        is_valid_ip_address(ip_type, ip.u128()),
+        is_valid_ip_address(
+            ip_type,
+            ip.u128(),
+            get_serving_allow_private_ips(deps.storage, netuid)
+        ),
         ContractError::InvalidIpAddress {}
     );
 
@@ -186,10 +192,14 @@ pub fn do_serve_prometheus(
         ContractError::NotRegistered {}
     );
 
-    // --- 3. Check the ip signature validity.
+    // --- 3. Check the ip signature validity, private ips only on subnets which allow them.
     ensure!(is_valid_ip_type(ip_type), ContractError::InvalidIpType {});
     ensure!(
-        is_valid_ip_address(ip_type, ip.u128()),
+        is_valid_ip_address(
+            ip_type,
+            ip.u128(),
+            get_serving_allow_private_ips(deps.storage, netuid)
+        ),
         ContractError::InvalidIpAddress {}
     );
 
@@ -301,37 +311,66 @@ pub fn is_valid_ip_type(ip_type: u8) -> bool {
     return allowed_values.contains(&ip_type);
 }
 
-// @todo (Parallax 2-1-2021) : Implement exclusion of private IP ranges
-pub fn is_valid_ip_address(ip_type: u8, addr: u128) -> bool {
+// Checks the ip is well formed for its type and, unless private ips are allowed, that it is
+// routable on the public internet.
+//
+pub fn is_valid_ip_address(ip_type: u8, addr: u128, allow_private: bool) -> bool {
     if !is_valid_ip_type(ip_type) {
         return false;
     }
     if addr == 0 {
         return false;
     }
-    if ip_type == 4 {
-        if addr == 0 {
-            return false;
-        }
-        if addr >= u32::MAX as u128 {
-            return false;
-        }
-        if addr == 0x7f000001 {
-            return false;
-        } // Localhost
+    if ip_type == 4 && addr >= u32::MAX as u128 {
+        return false;
     }
-    if ip_type == 6 {
-        if addr == 0x0 {
-            return false;
-        }
-        if addr == u128::MAX {
-            return false;
-        }
-        if addr == 1 {
-            return false;
-        } // IPv6 localhost
+    allow_private || !is_private_or_reserved_ip(ip_type, addr)
+}
+
+// Returns true for ips from private, loopback, link-local, shared (CGNAT), multicast,
+// documentation and other reserved ranges, which are not reachable from the public internet.
+//
+pub fn is_private_or_reserved_ip(ip_type: u8, addr: u128) -> bool {
+    match ip_type {
+        4 => is_private_or_reserved_ipv4(Ipv4Addr::from(addr as u32)),
+        6 => is_private_or_reserved_ipv6(Ipv6Addr::from(addr)),
+        _ => true,
     }
-    return true;
+}
+
+fn is_private_or_reserved_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    // 0.0.0.0/8 this network, 10.0.0.0/8 private, 127.0.0.0/8 loopback
+    a == 0 || a == 10 || a == 127
+        // 100.64.0.0/10 shared address space (CGNAT)
+        || (a == 100 && (b & 0xc0) == 64)
+        // 169.254.0.0/16 link-local
+        || (a == 169 && b == 254)
+        // 172.16.0.0/12 and 192.168.0.0/16 private
+        || (a == 172 && (b & 0xf0) == 16)
+        || (a == 192 && b == 168)
+        // 192.0.0.0/24 protocol assignments, 192.0.2.0/24 documentation
+        || (a == 192 && b == 0 && (c == 0 || c == 2))
+        // 198.18.0.0/15 benchmarking, 198.51.100.0/24 and 203.0.113.0/24 documentation
+        || (a == 198 && (b & 0xfe) == 18)
+        || (a == 198 && b == 51 && c == 100)
+        || (a == 203 && b == 0 && c == 113)
+        // 224.0.0.0/4 multicast, 240.0.0.0/4 reserved and broadcast
+        || a >= 224
+}
+
+fn is_private_or_reserved_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    // :: unspecified, ::1 loopback, ::ffff:0:0/96 ipv4-mapped
+    ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.to_ipv4_mapped().is_some()
+        // fe80::/10 link-local, fc00::/7 unique local, ff00::/8 multicast
+        || (first & 0xffc0) == 0xfe80
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xff00) == 0xff00
+        // 2001:db8::/32 documentation
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8)
 }
 
 pub fn validate_axon_data(axon_info: &AxonInfoOf) -> Result<bool, ContractError> {
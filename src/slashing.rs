@@ -0,0 +1,105 @@
+use std::ops::Deref;
+
+use cosmwasm_std::{coins, Addr, Api, BankMsg, Order, StdResult, Storage};
+
+use crate::staking::{
+    decrease_stake_on_coldkey_hotkey_account, get_owning_coldkey_for_hotkey,
+    get_stake_for_coldkey_and_hotkey,
+};
+use crate::state::{SlashingConfig, DENOM, KEYS, LOW_TRUST_EPOCHS, SLASHING, STAKE, VALIDATOR_PERMIT};
+use crate::utils::get_validator_trust_for_uid;
+use crate::ContractError;
+
+// Applies the subnet slashing policy right after its epoch. Validators whose trust stayed below
+// the threshold for the configured number of consecutive epochs lose a fraction of their stake.
+// Returns the messages which burn the slashed tokens or send them to the treasury.
+//
+pub fn slash_low_trust_validators(
+    store: &mut dyn Storage,
+    api: &dyn Api,
+    netuid: u16,
+) -> Result<Vec<BankMsg>, ContractError> {
+    // --- 1. Slashing is opt-in, nothing to do without a config.
+    let config = match SLASHING.may_load(store, netuid)? {
+        Some(config) => config,
+        None => return Ok(vec![]),
+    };
+
+    // --- 2. Update the low trust counters of validators and slash the ones over the limit.
+    let permits = VALIDATOR_PERMIT.may_load(store, netuid)?.unwrap_or_default();
+    let mut total_slashed: u64 = 0;
+    for (uid, permit) in permits.iter().enumerate() {
+        let uid = uid as u16;
+        let hotkey = match KEYS.may_load(store, (netuid, uid))? {
+            Some(hotkey) => hotkey,
+            None => continue, // Vacant slot of a deregistered neuron.
+        };
+        if !permit || get_validator_trust_for_uid(store, netuid, uid) >= config.trust_threshold {
+            LOW_TRUST_EPOCHS.remove(store, (netuid, uid));
+            continue;
+        }
+        let low_trust_epochs = LOW_TRUST_EPOCHS
+            .may_load(store, (netuid, uid))?
+            .unwrap_or_default()
+            .saturating_add(1);
+        if low_trust_epochs < config.epochs {
+            LOW_TRUST_EPOCHS.save(store, (netuid, uid), &low_trust_epochs)?;
+            continue;
+        }
+        LOW_TRUST_EPOCHS.remove(store, (netuid, uid));
+
+        let slashed = slash_hotkey(store, &config, &hotkey)?;
+        api.debug(&format!(
+            "🔪 slashed validator ( netuid: {:?} | uid: {:?} | hotkey: {:?} | amount: {:?} )",
+            netuid, uid, hotkey, slashed
+        ));
+        total_slashed = total_slashed.saturating_add(slashed);
+    }
+
+    if total_slashed == 0 {
+        return Ok(vec![]);
+    }
+
+    // --- 3. The slashed tokens left the stake, burn them or move them to the treasury.
+    let amount = coins(total_slashed as u128, DENOM.load(store)?);
+    let msg = match config.treasury {
+        Some(treasury) => BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount,
+        },
+        None => BankMsg::Burn { amount },
+    };
+
+    Ok(vec![msg])
+}
+
+// Removes the slashed fraction from the stake of the hotkey owner, or from every nominator
+// proportionally when the subnet shares slashing with nominators. Returns the slashed amount.
+//
+fn slash_hotkey(
+    store: &mut dyn Storage,
+    config: &SlashingConfig,
+    hotkey: &Addr,
+) -> Result<u64, ContractError> {
+    let stakers: Vec<Addr> = if config.share_with_nominators {
+        STAKE
+            .prefix(hotkey)
+            .keys(store.deref(), None, None, Order::Ascending)
+            .collect::<StdResult<Vec<Addr>>>()?
+    } else {
+        vec![get_owning_coldkey_for_hotkey(store, hotkey)]
+    };
+
+    let mut slashed: u64 = 0;
+    for coldkey in stakers {
+        let stake = get_stake_for_coldkey_and_hotkey(store, &coldkey, hotkey);
+        let amount = (stake as u128 * config.fraction as u128 / u16::MAX as u128) as u64;
+        if amount == 0 {
+            continue;
+        }
+        decrease_stake_on_coldkey_hotkey_account(store, &coldkey, hotkey, amount)?;
+        slashed = slashed.saturating_add(amount);
+    }
+
+    Ok(slashed)
+}
@@ -1,13 +1,31 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Deps, Order, StdResult, Storage};
+use cosmwasm_std::{Addr, Decimal, Deps, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
 
-use crate::state::STAKE;
+use crate::staking::{
+    get_accrued_rewards_for_coldkey_and_hotkey, get_stake_for_coldkey_and_hotkey,
+};
+use crate::state::{HOTKEY_REWARDS_HISTORY, STAKE};
+
+// Max number of days returned by a page of the rewards history query.
+const MAX_REWARDS_HISTORY_LIMIT: u16 = 365;
 
 #[cw_serde]
 pub struct StakeInfo {
     pub hotkey: Addr,
     pub coldkey: Addr,
     pub stake: u64,
+    pub rewards: u64, // Cumulative rewards credited to the stake
+}
+
+#[cw_serde]
+pub struct RewardsHistory {
+    pub hotkey: Addr,
+    pub coldkey: Addr,
+    pub stake: u64,
+    pub rewards: u64,
+    // Vec of (day, reward per unit of stake) distributed by the delegate to nominators
+    pub reward_per_share: Vec<(u64, Decimal)>,
 }
 
 fn _get_stake_info_for_coldkeys(
@@ -23,12 +41,15 @@ fn _get_stake_info_for_coldkeys(
         let mut stake_info_for_coldkey: Vec<StakeInfo> = Vec::new();
 
         for item in STAKE.range(store, None, None, Order::Ascending) {
-            let ((hotkey, coldkey), stake) = item?;
+            let ((hotkey, coldkey), _) = item?;
             if coldkey == coldkey_ {
+                let stake = get_stake_for_coldkey_and_hotkey(store, &coldkey, &hotkey);
+                let rewards = get_accrued_rewards_for_coldkey_and_hotkey(store, &coldkey, &hotkey);
                 stake_info_for_coldkey.push(StakeInfo {
                     hotkey,
                     coldkey,
                     stake,
+                    rewards,
                 });
             }
         }
@@ -72,3 +93,30 @@ pub fn get_stake_info_for_coldkey(
         Ok(stake_info.get(0).unwrap().1.clone())
     };
 }
+
+pub fn get_rewards_history(
+    deps: Deps,
+    hotkey_account: String,
+    coldkey_account: String,
+    start_after: Option<u64>,
+    limit: Option<u16>,
+) -> StdResult<RewardsHistory> {
+    let hotkey = deps.api.addr_validate(&hotkey_account)?;
+    let coldkey = deps.api.addr_validate(&coldkey_account)?;
+    let start = start_after.map(Bound::exclusive);
+    let history_limit = limit.unwrap_or(32).min(MAX_REWARDS_HISTORY_LIMIT) as usize;
+
+    let reward_per_share = HOTKEY_REWARDS_HISTORY
+        .prefix(&hotkey)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(history_limit)
+        .collect::<StdResult<Vec<(u64, Decimal)>>>()?;
+
+    Ok(RewardsHistory {
+        stake: get_stake_for_coldkey_and_hotkey(deps.storage, &coldkey, &hotkey),
+        rewards: get_accrued_rewards_for_coldkey_and_hotkey(deps.storage, &coldkey, &hotkey),
+        hotkey,
+        coldkey,
+        reward_per_share,
+    })
+}
@@ -1,17 +1,26 @@
 use std::ops::{Deref, Mul};
 use std::str::FromStr;
 
-use cosmwasm_std::{coins, ensure, Addr, BankMsg, CosmosMsg, DepsMut, Env, MessageInfo, Order, StdResult, Storage, Uint128, Decimal};
+use cosmwasm_std::{coins, ensure, Addr, BankMsg, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Order, StdResult, Storage, Uint128, Decimal};
+use cw_storage_plus::Bound;
 use cw_utils::must_pay;
 
 use crate::state::{
-    DELEGATES, DENOM, OWNER, STAKE, TOTAL_COLDKEY_STAKE, TOTAL_HOTKEY_STAKE, TOTAL_ISSUANCE,
-    TOTAL_STAKE, COMMISSION_CHANGE,
+    DelegateIdentity, DelegateLimits, RewardPreference, DELEGATE_LIMITS, StakeLock, ACCRUED_REWARDS, HOTKEY_LOCK_BONUS, STAKE_LOCKS, STAKE_LOCKS_BY_UNLOCK, CLAIMABLE, HOTKEY_PAYOUT_STAKE, HOTKEY_PENDING_PAYOUTS,
+    REWARD_PREFERENCE, TOTAL_CLAIMABLE, HOTKEY_REWARDS_HISTORY, DELEGATES, DELEGATES_BY_STAKE, DELEGATE_IDENTITY, DENOM, HOTKEY_PENDING_REWARDS,
+    HOTKEY_OWNER_APPROVALS, HOTKEY_REWARD_INDEX, NOMINATOR_REWARD_INDEX, OWNER, STAKE, STAKED_HOTKEYS, TOTAL_COLDKEY_STAKE, TOTAL_HOTKEY_STAKE, TOTAL_ISSUANCE,
+    TOTAL_STAKE, COMMISSION_CHANGE, UNISSUED_CLAIMABLE,
+};
+use crate::utils::{
+    exceeds_tx_rate_limit, get_blocks_per_day, get_default_take, get_last_tx_block, get_max_lock_bonus,
+    get_max_lock_duration, set_last_tx_block,
 };
-use crate::utils::{exceeds_tx_rate_limit, get_default_take, get_last_tx_block, set_last_tx_block};
 use crate::ContractError;
 use cyber_std::Response;
 
+// Number of days of the delegate rewards history kept.
+const REWARDS_HISTORY_DAYS: u64 = 365;
+
 // ---- The implementation for the extrinsic become_delegate: signals that this hotkey allows delegated stake.
 //
 // # Args:
@@ -145,6 +154,54 @@ pub fn do_add_stake(
 ) -> Result<Response, ContractError> {
     // --- 1. We check that the transaction is signed by the caller and retrieve the T::AccountId coldkey information.
     let coldkey = info.clone().sender;
+    add_stake_for_coldkey(deps, env, info, hotkey_address, coldkey)
+}
+
+// ---- The implementation for the extrinsic add_stake_for: Adds stake paid by the caller to a hotkey account
+// on behalf of the beneficiary coldkey. Only the beneficiary can remove the stake afterwards.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the paying account.
+//
+// 	* 'hotkey' (T::AccountId):
+// 		- The associated hotkey account.
+//
+// 	* 'beneficiary_coldkey' (T::AccountId):
+// 		- The coldkey credited with the stake.
+//
+// # Event:
+// 	* StakeAdded;
+// 		- On the successfully adding stake to a global account.
+//
+// # Raises:
+// 	* 'CouldNotConvertToBalance':
+// 		- Unable to convert the passed stake value to a balance.
+//
+// 	* 'NonAssociatedColdKey':
+// 		- The hotkey is not a delegate and the beneficiary coldkey is not associated with it.
+//
+// 	* 'TxRateLimitExceeded':
+// 		- Thrown if the paying key has hit transaction rate limit
+//
+pub fn do_add_stake_for(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hotkey_address: String,
+    beneficiary_coldkey: String,
+) -> Result<Response, ContractError> {
+    let coldkey = deps.api.addr_validate(&beneficiary_coldkey)?;
+    add_stake_for_coldkey(deps, env, info, hotkey_address, coldkey)
+}
+
+fn add_stake_for_coldkey(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hotkey_address: String,
+    coldkey: Addr,
+) -> Result<Response, ContractError> {
     let hotkey = deps.api.addr_validate(&hotkey_address)?;
 
     let denom = DENOM.load(deps.storage)?;
@@ -152,8 +209,8 @@ pub fn do_add_stake(
         must_pay(&info, &denom).map_err(|_| ContractError::CouldNotConvertToBalance {})?;
 
     deps.api.debug(&format!(
-        "🌐 do_add_stake ( coldkey:{:?}, hotkey:{:?}, stake_to_be_added:{:?} )",
-        coldkey, hotkey, stake_to_be_added
+        "🌐 do_add_stake ( sender:{:?}, coldkey:{:?}, hotkey:{:?}, stake_to_be_added:{:?} )",
+        info.sender, coldkey, hotkey, stake_to_be_added
     ));
 
     // --- 4. Ensure that the hotkey account exists this is only possible through registration.
@@ -169,10 +226,13 @@ pub fn do_add_stake(
         ContractError::NonAssociatedColdKey {}
     );
 
+    // --- 6. Ensure that the delegate accepts the stake.
+    ensure_delegate_accepts_stake(deps.storage, &coldkey, &hotkey, stake_to_be_added.u128() as u64)?;
+
     ensure!(
         !exceeds_tx_rate_limit(
             deps.storage,
-            get_last_tx_block(deps.storage, &coldkey),
+            get_last_tx_block(deps.storage, &info.sender),
             env.block.height
         ),
         ContractError::TxRateLimitExceeded {}
@@ -197,6 +257,7 @@ pub fn do_add_stake(
     Ok(Response::default()
         .add_attribute("action", "stake_added")
         .add_attribute("hotkey", hotkey)
+        .add_attribute("coldkey", coldkey)
         .add_attribute("take", format!("{:?}", stake_to_be_added)))
 }
 
@@ -274,6 +335,14 @@ pub fn do_remove_stake(
         ContractError::NotEnoughStaketoWithdraw {}
     );
 
+    // --- 5. Ensure that the stake to withdraw is not locked.
+    let locked_stake = get_locked_stake(deps.storage, &coldkey, &hotkey, env.block.height);
+    ensure!(
+        get_stake_for_coldkey_and_hotkey(deps.storage, &coldkey, &hotkey).saturating_sub(locked_stake)
+            >= stake_to_be_removed,
+        ContractError::StakeLocked {}
+    );
+
     // --- 6. Ensure we don't exceed tx rate limit
     ensure!(
         !exceeds_tx_rate_limit(
@@ -308,6 +377,185 @@ pub fn do_remove_stake(
         .add_attribute("stake_to_be_removed", format!("{}", stake_to_be_removed)))
 }
 
+// ---- The implementation for the extrinsic add_stake_multi: Adds stake to several hotkey accounts,
+// the paid funds must match the sum of the allocations.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the caller's coldkey.
+//
+// 	* 'allocations' (Vec<(T::AccountId, u64)>):
+// 		- The hotkeys and the amounts of stake to add to them.
+//
+// # Raises:
+// 	* 'InvalidAllocations':
+// 		- The allocations are empty, have a zero amount or don't match the paid funds.
+//
+// 	* 'NotRegistered':
+// 		- One of the hotkeys is not registered on the network.
+//
+// 	* 'NonAssociatedColdKey':
+// 		- One of the hotkeys is not a delegate and not owned by the calling coldkey.
+//
+// 	* 'TxRateLimitExceeded':
+// 		- Thrown if key has hit transaction rate limit, checked once for the batch.
+//
+pub fn do_add_stake_multi(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    allocations: Vec<(String, u64)>,
+) -> Result<Response, ContractError> {
+    // --- 1. We check the signature and the paid funds.
+    let coldkey = info.clone().sender;
+    let denom = DENOM.load(deps.storage)?;
+    let paid = must_pay(&info, &denom).map_err(|_| ContractError::CouldNotConvertToBalance {})?;
+
+    let total: u128 = allocations.iter().map(|(_, amount)| *amount as u128).sum();
+    ensure!(
+        !allocations.is_empty()
+            && allocations.iter().all(|(_, amount)| *amount > 0)
+            && paid.u128() == total,
+        ContractError::InvalidAllocations {}
+    );
+
+    // --- 2. One rate limit check for the whole batch.
+    ensure!(
+        !exceeds_tx_rate_limit(
+            deps.storage,
+            get_last_tx_block(deps.storage, &coldkey),
+            env.block.height
+        ),
+        ContractError::TxRateLimitExceeded {}
+    );
+
+    // --- 3. Add the stake to each hotkey.
+    for (hotkey_address, amount) in allocations.iter() {
+        let hotkey = deps.api.addr_validate(hotkey_address)?;
+        ensure!(
+            hotkey_account_exists(deps.storage, &hotkey),
+            ContractError::NotRegistered {}
+        );
+        ensure!(
+            hotkey_is_delegate(deps.storage, &hotkey)
+                || coldkey_owns_hotkey(deps.storage, &coldkey, &hotkey),
+            ContractError::NonAssociatedColdKey {}
+        );
+        ensure_delegate_accepts_stake(deps.storage, &coldkey, &hotkey, *amount)?;
+        increase_stake_on_coldkey_hotkey_account(deps.storage, &coldkey, &hotkey, *amount);
+    }
+
+    // Set last block for rate limiting
+    set_last_tx_block(deps.storage, &coldkey, env.block.height);
+
+    deps.api.debug(&format!(
+        "🌐 StakeMultiAdded ( coldkey:{:?}, allocations:{:?} )",
+        coldkey, allocations
+    ));
+
+    // --- 4. Ok and return.
+    Ok(Response::default()
+        .add_attribute("action", "stake_multi_added")
+        .add_attribute("coldkey", coldkey)
+        .add_attribute("hotkeys", format!("{}", allocations.len()))
+        .add_attribute("stake_to_be_added", format!("{}", total)))
+}
+
+// ---- The implementation for the extrinsic remove_stake_multi: Removes stake from several hotkey
+// accounts and sends the total to the coldkey.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the caller's coldkey.
+//
+// 	* 'allocations' (Vec<(T::AccountId, u64)>):
+// 		- The hotkeys and the amounts of stake to remove from them.
+//
+// # Raises:
+// 	* 'InvalidAllocations':
+// 		- The allocations are empty or have a zero amount.
+//
+// 	* 'NotRegistered':
+// 		- One of the hotkeys is not registered on the network.
+//
+// 	* 'NotEnoughStaketoWithdraw':
+// 		- Thrown if there is not enough stake on one of the hotkeys.
+//
+// 	* 'StakeLocked':
+// 		- Thrown if the stake to remove from one of the hotkeys is locked.
+//
+// 	* 'TxRateLimitExceeded':
+// 		- Thrown if key has hit transaction rate limit, checked once for the batch.
+//
+pub fn do_remove_stake_multi(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    allocations: Vec<(String, u64)>,
+) -> Result<Response, ContractError> {
+    // --- 1. We check the signature and the allocations.
+    let coldkey = info.clone().sender;
+    ensure!(
+        !allocations.is_empty() && allocations.iter().all(|(_, amount)| *amount > 0),
+        ContractError::InvalidAllocations {}
+    );
+
+    // --- 2. One rate limit check for the whole batch.
+    ensure!(
+        !exceeds_tx_rate_limit(
+            deps.storage,
+            get_last_tx_block(deps.storage, &coldkey),
+            env.block.height
+        ),
+        ContractError::TxRateLimitExceeded {}
+    );
+
+    // --- 3. Remove the stake from each hotkey.
+    let mut total: u64 = 0;
+    for (hotkey_address, amount) in allocations.iter() {
+        let hotkey = deps.api.addr_validate(hotkey_address)?;
+        ensure!(
+            hotkey_account_exists(deps.storage, &hotkey),
+            ContractError::NotRegistered {}
+        );
+        ensure!(
+            has_enough_stake(deps.storage, &coldkey, &hotkey, *amount),
+            ContractError::NotEnoughStaketoWithdraw {}
+        );
+        let locked_stake = get_locked_stake(deps.storage, &coldkey, &hotkey, env.block.height);
+        ensure!(
+            get_stake_for_coldkey_and_hotkey(deps.storage, &coldkey, &hotkey).saturating_sub(locked_stake)
+                >= *amount,
+            ContractError::StakeLocked {}
+        );
+        decrease_stake_on_coldkey_hotkey_account(deps.storage, &coldkey, &hotkey, *amount)?;
+        total = total.saturating_add(*amount);
+    }
+
+    // Set last block for rate limiting
+    set_last_tx_block(deps.storage, &coldkey, env.block.height);
+
+    // --- 4. We add the balance to the coldkey
+    let denom = DENOM.load(deps.storage)?;
+    let msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: coldkey.to_string(),
+        amount: coins(Uint128::from(total).u128(), denom),
+    });
+
+    deps.api.debug(&format!(
+        "🌐 StakeMultiRemoved ( coldkey:{:?}, allocations:{:?} )",
+        coldkey, allocations
+    ));
+
+    // --- 5. Done and ok.
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("action", "stake_multi_removed")
+        .add_attribute("coldkey", coldkey)
+        .add_attribute("hotkeys", format!("{}", allocations.len()))
+        .add_attribute("stake_to_be_removed", format!("{}", total)))
+}
+
 pub fn do_set_delegate_commission(
     deps: DepsMut,
     env: Env,
@@ -380,111 +628,993 @@ pub fn do_set_delegate_commission(
         .add_attribute("commission", format!("{}", commission)))
 }
 
-// Returns true if the passed hotkey allow delegative staking.
+// ---- The implementation for the extrinsic set_delegate_identity: publishes the identity of a delegate.
 //
-pub fn hotkey_is_delegate(store: &dyn Storage, hotkey: &Addr) -> bool {
-    DELEGATES.has(store, hotkey)
-}
-
-// Sets the hotkey as a delegate with take.
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the caller's coldkey.
 //
-pub fn delegate_hotkey(store: &mut dyn Storage, hotkey: &Addr, take: u16) {
-    DELEGATES.save(store, hotkey, &take).unwrap();
-}
-
-// Returns the total amount of stake in the staking table.
-#[cfg(test)]
-pub fn get_total_stake(store: &dyn Storage) -> u64 {
-    return TOTAL_STAKE.load(store).unwrap();
-}
+// 	* 'hotkey' (T::AccountId):
+// 		- The delegate hotkey (must be owned by the coldkey.)
+//
+// 	* 'identity' (DelegateIdentity):
+// 		- The name, url, description and image particles and contact of the delegate.
+//
+// # Raises:
+// 	* 'NotRegistered':
+// 		- The hotkey is not registered on the network.
+//
+// 	* 'NonAssociatedColdKey':
+// 		- The hotkey is not owned by the calling coldkey.
+//
+// 	* 'NotDelegate':
+// 		- The hotkey is not a delegate.
+//
+// 	* 'MetadataError':
+// 		- The identity fields are malformed.
+//
+pub fn do_set_delegate_identity(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hotkey_address: String,
+    identity: DelegateIdentity,
+) -> Result<Response, ContractError> {
+    // --- 1. We check the coldkey signuture.
+    let coldkey = info.sender;
+    let hotkey = deps.api.addr_validate(&hotkey_address)?;
 
-// Increases the total amount of stake by the passed amount.
-#[cfg(test)]
-pub fn increase_total_stake(store: &mut dyn Storage, increment: u64) {
-    TOTAL_STAKE
-        .update(store, |s| -> StdResult<_> {
-            Ok(s.saturating_add(increment))
-        })
-        .unwrap();
-}
+    deps.api.debug(&format!(
+        "🌐 do_set_delegate_identity ( coldkey:{:?} hotkey:{:?}, name:{:?} )",
+        coldkey, hotkey, identity.name
+    ));
 
-// Decreases the total amount of stake by the passed amount.
-#[cfg(test)]
-pub fn decrease_total_stake(store: &mut dyn Storage, decrement: u64) {
-    TOTAL_STAKE
-        .update(store, |s| -> StdResult<_> {
-            Ok(s.saturating_sub(decrement))
-        })
-        .unwrap();
-}
+    // --- 2. Ensure we are setting identity for a known key.
+    ensure!(
+        hotkey_account_exists(deps.storage, &hotkey),
+        ContractError::NotRegistered {}
+    );
 
-// Returns the total amount of stake under a hotkey (delegative or otherwise)
-//
-pub fn get_total_stake_for_hotkey(store: &dyn Storage, hotkey: &Addr) -> u64 {
-    // TODO revisit and delete default
-    return TOTAL_HOTKEY_STAKE.load(store, hotkey).unwrap();
-}
+    // --- 3. Ensure that the coldkey is the owner.
+    ensure!(
+        coldkey_owns_hotkey(deps.storage, &coldkey, &hotkey),
+        ContractError::NonAssociatedColdKey {}
+    );
 
-// Returns the total amount of stake held by the coldkey (delegative or otherwise)
-#[cfg(test)]
-pub fn get_total_stake_for_coldkey(store: &dyn Storage, coldkey: &Addr) -> u64 {
-    return TOTAL_COLDKEY_STAKE.load(store, coldkey).unwrap();
-}
+    // --- 4. Ensure that the hotkey is a delegate.
+    ensure!(
+        hotkey_is_delegate(deps.storage, &hotkey),
+        ContractError::NotDelegate {}
+    );
 
-// Returns the stake under the cold - hot pairing in the staking table.
-//
-pub fn get_stake_for_coldkey_and_hotkey(store: &dyn Storage, coldkey: &Addr, hotkey: &Addr) -> u64 {
-    // Added default, see delegate_info:125
-    STAKE.load(store, (hotkey, coldkey)).unwrap_or_default()
-}
+    // --- 5. Validate the identity fields.
+    ensure!(
+        !identity.name.is_empty() && identity.name.len() <= 32,
+        ContractError::MetadataError {}
+    );
+    ensure!(identity.url.len() <= 256, ContractError::MetadataError {});
+    ensure!(identity.description.len() == 46, ContractError::MetadataError {});
+    ensure!(identity.image.len() == 46, ContractError::MetadataError {});
+    ensure!(identity.contact.len() <= 256, ContractError::MetadataError {});
 
-// Creates a cold - hot pairing account if the hotkey is not already an active account.
-//
-pub fn create_account_if_non_existent(store: &mut dyn Storage, coldkey: &Addr, hotkey: &Addr) {
-    if !hotkey_account_exists(store, hotkey) {
-        STAKE.save(store, (hotkey, coldkey), &0).unwrap();
-        OWNER.save(store, hotkey, coldkey).unwrap();
-        TOTAL_HOTKEY_STAKE.save(store, hotkey, &0u64).unwrap();
-        TOTAL_COLDKEY_STAKE.save(store, coldkey, &0u64).unwrap();
-    }
-}
+    // --- 6. Save the identity.
+    DELEGATE_IDENTITY.save(deps.storage, &hotkey, &identity)?;
 
-// Returns the coldkey owning this hotkey. This function should only be called for active accounts.
-//
-pub fn get_owning_coldkey_for_hotkey(store: &dyn Storage, hotkey: &Addr) -> Addr {
-    return OWNER.load(store, hotkey).unwrap();
-}
+    deps.api.debug(&format!(
+        "🌐 DelegateIdentitySet( coldkey:{:?}, hotkey:{:?}, name:{:?} )",
+        coldkey, hotkey, identity.name
+    ));
 
-pub fn hotkey_account_exists(store: &dyn Storage, hotkey: &Addr) -> bool {
-    return OWNER.has(store, hotkey);
+    // --- 7. Ok and return.
+    Ok(Response::default()
+        .add_attribute("action", "delegate_identity_set")
+        .add_attribute("hotkey", hotkey)
+        .add_attribute("name", identity.name))
 }
 
-// Return true if the passed coldkey owns the hotkey.
+// ---- The implementation for the extrinsic set_delegate_limits: sets the max stake the delegate accepts
+// from nominators and whether it is closed to new nominators. Existing nominators can always unstake.
 //
-pub fn coldkey_owns_hotkey(store: &dyn Storage, coldkey: &Addr, hotkey: &Addr) -> bool {
-    if OWNER.has(store, hotkey) {
-        return OWNER.load(store, hotkey).unwrap().eq(coldkey);
-    } else {
-        return false;
-    }
-}
-
-// Returns true if the cold-hot staking account has enough balance to fufil the decrement.
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the caller's coldkey.
 //
-pub fn has_enough_stake(
-    store: &dyn Storage,
-    coldkey: &Addr,
-    hotkey: &Addr,
-    decrement: u64,
-) -> bool {
-    return get_stake_for_coldkey_and_hotkey(store, coldkey, hotkey) >= decrement;
+// 	* 'hotkey' (T::AccountId):
+// 		- The delegate hotkey (must be owned by the coldkey.)
+//
+// 	* 'limits' (DelegateLimits):
+// 		- The max delegated stake and the closed flag.
+//
+// # Raises:
+// 	* 'NotRegistered':
+// 		- The hotkey is not registered on the network.
+//
+// 	* 'NonAssociatedColdKey':
+// 		- The hotkey is not owned by the calling coldkey.
+//
+// 	* 'NotDelegate':
+// 		- The hotkey is not a delegate.
+//
+pub fn do_set_delegate_limits(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hotkey_address: String,
+    limits: DelegateLimits,
+) -> Result<Response, ContractError> {
+    // --- 1. We check the coldkey signuture.
+    let coldkey = info.sender;
+    let hotkey = deps.api.addr_validate(&hotkey_address)?;
+
+    // --- 2. Ensure we are setting limits for a known key.
+    ensure!(
+        hotkey_account_exists(deps.storage, &hotkey),
+        ContractError::NotRegistered {}
+    );
+
+    // --- 3. Ensure that the coldkey is the owner.
+    ensure!(
+        coldkey_owns_hotkey(deps.storage, &coldkey, &hotkey),
+        ContractError::NonAssociatedColdKey {}
+    );
+
+    // --- 4. Ensure that the hotkey is a delegate.
+    ensure!(
+        hotkey_is_delegate(deps.storage, &hotkey),
+        ContractError::NotDelegate {}
+    );
+
+    // --- 5. Save the limits, no limits are kept as absent.
+    if limits == DelegateLimits::default() {
+        DELEGATE_LIMITS.remove(deps.storage, &hotkey);
+    } else {
+        DELEGATE_LIMITS.save(deps.storage, &hotkey, &limits)?;
+    }
+
+    deps.api.debug(&format!(
+        "🌐 DelegateLimitsSet( coldkey:{:?}, hotkey:{:?}, limits:{:?} )",
+        coldkey, hotkey, limits
+    ));
+
+    // --- 6. Ok and return.
+    Ok(Response::default()
+        .add_attribute("action", "delegate_limits_set")
+        .add_attribute("hotkey", hotkey)
+        .add_attribute("closed", format!("{}", limits.closed))
+        .add_attribute(
+            "max_delegated_stake",
+            limits
+                .max_delegated_stake
+                .map_or("none".to_string(), |max| format!("{}", max)),
+        ))
+}
+
+// ---- The implementation for the extrinsic approve_hotkey_owner: the hotkey approves the coldkey
+// which may claim its ownership by registering it. Protects hotkeys from being squatted.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the hotkey.
+//
+// 	* 'coldkey' (T::AccountId):
+// 		- The coldkey allowed to become the owner of the hotkey.
+//
+// # Raises:
+// 	* 'AlreadyRegistered':
+// 		- The hotkey already has an owner.
+//
+pub fn do_approve_hotkey_owner(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    coldkey_address: String,
+) -> Result<Response, ContractError> {
+    // --- 1. We check the hotkey signuture.
+    let hotkey = info.sender;
+    let coldkey = deps.api.addr_validate(&coldkey_address)?;
+
+    // --- 2. Ensure that the hotkey is not owned yet, ownership can't be transferred.
+    ensure!(
+        !hotkey_account_exists(deps.storage, &hotkey),
+        ContractError::AlreadyRegistered {}
+    );
+
+    // --- 3. Save the approval, it is consumed when the account is created.
+    HOTKEY_OWNER_APPROVALS.save(deps.storage, &hotkey, &coldkey)?;
+
+    deps.api.debug(&format!(
+        "🌐 HotkeyOwnerApproved( hotkey:{:?}, coldkey:{:?} )",
+        hotkey, coldkey
+    ));
+
+    // --- 4. Ok and return.
+    Ok(Response::default()
+        .add_attribute("action", "hotkey_owner_approved")
+        .add_attribute("hotkey", hotkey)
+        .add_attribute("coldkey", coldkey))
+}
+
+// Checks that the delegate accepts the stake of the coldkey. The owner stake is not limited,
+// closed delegates accept stake only from existing nominators.
+//
+pub fn ensure_delegate_accepts_stake(
+    store: &dyn Storage,
+    coldkey: &Addr,
+    hotkey: &Addr,
+    amount: u64,
+) -> Result<(), ContractError> {
+    let limits = match DELEGATE_LIMITS.may_load(store, hotkey)? {
+        Some(limits) => limits,
+        None => return Ok(()),
+    };
+    let owner = get_owning_coldkey_for_hotkey(store, hotkey);
+    if *coldkey == owner {
+        return Ok(());
+    }
+
+    ensure!(
+        !limits.closed || STAKED_HOTKEYS.has(store, (coldkey, hotkey)),
+        ContractError::DelegateClosed {}
+    );
+    if let Some(max_delegated_stake) = limits.max_delegated_stake {
+        let delegated_stake = get_total_stake_for_hotkey(store, hotkey)
+            .saturating_sub(get_stake_for_coldkey_and_hotkey(store, &owner, hotkey));
+        ensure!(
+            delegated_stake.saturating_add(amount) <= max_delegated_stake,
+            ContractError::DelegateCapacityExceeded {}
+        );
+    }
+
+    Ok(())
+}
+
+// ---- The implementation for the extrinsic set_reward_preference: sets how the rewards of the caller
+// on a hotkey are credited, compounded into the stake or paid out to the claimable balance.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the caller's coldkey.
+//
+// 	* 'hotkey' (T::AccountId):
+// 		- The hotkey the coldkey stakes on.
+//
+// 	* 'preference' (RewardPreference):
+// 		- Compound or pay out the rewards.
+//
+// # Raises:
+// 	* 'NotRegistered':
+// 		- The hotkey is not registered on the network.
+//
+pub fn do_set_reward_preference(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hotkey_address: String,
+    preference: RewardPreference,
+) -> Result<Response, ContractError> {
+    // --- 1. We check the signature.
+    let coldkey = info.sender;
+    let hotkey = deps.api.addr_validate(&hotkey_address)?;
+
+    // --- 2. Ensure that the hotkey account exists.
+    ensure!(
+        hotkey_account_exists(deps.storage, &hotkey),
+        ContractError::NotRegistered {}
+    );
+
+    // --- 3. Settle the rewards accrued with the previous preference.
+    settle_nominator_rewards(deps.storage, &coldkey, &hotkey);
+
+    // --- 4. Move the settled stake in or out of the delegate payout stake.
+    let stake = STAKE.may_load(deps.storage, (&hotkey, &coldkey))?.unwrap_or_default();
+    let was_payout = is_payout_preference(deps.storage, &coldkey, &hotkey);
+    match preference {
+        RewardPreference::Payout if !was_payout => {
+            HOTKEY_PAYOUT_STAKE.update(deps.storage, &hotkey, |s| -> StdResult<_> {
+                Ok(s.unwrap_or_default().saturating_add(stake))
+            })?;
+        }
+        RewardPreference::Compound if was_payout => {
+            HOTKEY_PAYOUT_STAKE.update(deps.storage, &hotkey, |s| -> StdResult<_> {
+                Ok(s.unwrap_or_default().saturating_sub(stake))
+            })?;
+        }
+        _ => {}
+    }
+
+    // --- 5. Compound is the default, keep only paid out preferences.
+    match preference {
+        RewardPreference::Payout => {
+            REWARD_PREFERENCE.save(deps.storage, (&hotkey, &coldkey), &preference)?
+        }
+        RewardPreference::Compound => REWARD_PREFERENCE.remove(deps.storage, (&hotkey, &coldkey)),
+    }
+
+    deps.api.debug(&format!(
+        "🌐 RewardPreferenceSet( coldkey:{:?}, hotkey:{:?}, preference:{:?} )",
+        coldkey, hotkey, preference
+    ));
+
+    // --- 6. Ok and return.
+    Ok(Response::default()
+        .add_attribute("action", "reward_preference_set")
+        .add_attribute("hotkey", hotkey)
+        .add_attribute("preference", format!("{:?}", preference)))
+}
+
+// ---- The implementation for the extrinsic lock_stake: locks stake of the caller on a hotkey until
+// a block. Locked stake can't be removed and counts with a bonus growing with the lock duration
+// for validator permits and root network stake. Locking again adds to the locked amount and
+// can only extend the unlock block.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the caller's coldkey.
+//
+// 	* 'hotkey' (T::AccountId):
+// 		- The hotkey the coldkey stakes on.
+//
+// 	* 'amount' (u64):
+// 		- The amount of stake to add to the lock.
+//
+// 	* 'duration' (u64):
+// 		- The number of blocks to lock the stake for.
+//
+// # Raises:
+// 	* 'NotRegistered':
+// 		- The hotkey is not registered on the network.
+//
+// 	* 'InvalidLockDuration':
+// 		- The duration is zero or above the max lock duration.
+//
+// 	* 'NotEnoughStakeToLock':
+// 		- The coldkey doesn't have enough stake on the hotkey.
+//
+pub fn do_lock_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    hotkey_address: String,
+    amount: u64,
+    duration: u64,
+) -> Result<Response, ContractError> {
+    // --- 1. We check the signature.
+    let coldkey = info.sender;
+    let hotkey = deps.api.addr_validate(&hotkey_address)?;
+    let block_number = env.block.height;
+
+    // --- 2. Ensure that the hotkey account exists.
+    ensure!(
+        hotkey_account_exists(deps.storage, &hotkey),
+        ContractError::NotRegistered {}
+    );
+
+    // --- 3. Ensure the duration is in range.
+    let max_lock_duration = get_max_lock_duration(deps.storage);
+    ensure!(
+        duration > 0 && duration <= max_lock_duration,
+        ContractError::InvalidLockDuration {}
+    );
+
+    // --- 4. Ensure the coldkey has the stake to lock, expired locks count as unlocked.
+    let locked_stake = get_locked_stake(deps.storage, &coldkey, &hotkey, block_number);
+    let lock_amount = locked_stake.saturating_add(amount);
+    ensure!(
+        amount > 0
+            && lock_amount <= get_stake_for_coldkey_and_hotkey(deps.storage, &coldkey, &hotkey),
+        ContractError::NotEnoughStakeToLock {}
+    );
+
+    // --- 5. Replace the previous lock, the unlock block can only be extended.
+    let unlock_block = STAKE_LOCKS
+        .may_load(deps.storage, (&hotkey, &coldkey))?
+        .map_or(0, |lock| lock.unlock_block)
+        .max(block_number.saturating_add(duration));
+    remove_stake_lock(deps.storage, &coldkey, &hotkey);
+
+    // --- 6. The bonus grows linearly with the lock duration up to the max lock bonus.
+    let bonus = (lock_amount as u128 * get_max_lock_bonus(deps.storage) as u128
+        / u16::MAX as u128
+        * (unlock_block - block_number) as u128
+        / max_lock_duration as u128) as u64;
+    let lock = StakeLock {
+        amount: lock_amount,
+        unlock_block,
+        bonus,
+    };
+    save_stake_lock(deps.storage, &coldkey, &hotkey, &lock)?;
+
+    deps.api.debug(&format!(
+        "🌐 StakeLocked ( coldkey:{:?}, hotkey:{:?}, amount:{:?}, unlock_block:{:?}, bonus:{:?} )",
+        coldkey, hotkey, lock_amount, unlock_block, bonus
+    ));
+
+    // --- 7. Ok and return.
+    Ok(Response::default()
+        .add_attribute("action", "stake_locked")
+        .add_attribute("hotkey", hotkey)
+        .add_attribute("amount", format!("{}", lock_amount))
+        .add_attribute("unlock_block", format!("{}", unlock_block)))
+}
+
+// Saves the lock of the cold - hot pairing and indexes it by the unlock block.
+// The previous lock of the pairing must be removed first.
+//
+fn save_stake_lock(
+    store: &mut dyn Storage,
+    coldkey: &Addr,
+    hotkey: &Addr,
+    lock: &StakeLock,
+) -> StdResult<()> {
+    STAKE_LOCKS.save(store, (hotkey, coldkey), lock)?;
+    STAKE_LOCKS_BY_UNLOCK.update(store, lock.unlock_block, |locks| -> StdResult<_> {
+        let mut locks = locks.unwrap_or_default();
+        locks.push((hotkey.clone(), coldkey.clone()));
+        Ok(locks)
+    })?;
+    HOTKEY_LOCK_BONUS.update(store, hotkey, |b| -> StdResult<_> {
+        Ok(b.unwrap_or_default().saturating_add(lock.bonus))
+    })?;
+    Ok(())
+}
+
+// Locks the amount of stake of the cold - hot pairing until the unlock block without a bonus.
+// The amount adds to the active lock of the pairing, which keeps its bonus and later unlock block.
+//
+pub fn add_stake_lock(
+    store: &mut dyn Storage,
+    coldkey: &Addr,
+    hotkey: &Addr,
+    amount: u64,
+    unlock_block: u64,
+    block_number: u64,
+) -> StdResult<()> {
+    let lock = match STAKE_LOCKS.may_load(store, (hotkey, coldkey))? {
+        Some(lock) if lock.unlock_block > block_number => StakeLock {
+            amount: lock.amount.saturating_add(amount),
+            unlock_block: lock.unlock_block.max(unlock_block),
+            bonus: lock.bonus,
+        },
+        _ => StakeLock {
+            amount,
+            unlock_block,
+            bonus: 0,
+        },
+    };
+    remove_stake_lock(store, coldkey, hotkey);
+    save_stake_lock(store, coldkey, hotkey, &lock)
+}
+
+// Returns the stake of the cold - hot pairing which is locked at the block.
+//
+pub fn get_locked_stake(store: &dyn Storage, coldkey: &Addr, hotkey: &Addr, block_number: u64) -> u64 {
+    match STAKE_LOCKS.may_load(store, (hotkey, coldkey)).unwrap() {
+        Some(lock) if lock.unlock_block > block_number => lock.amount,
+        _ => 0,
+    }
+}
+
+// Returns the total stake of the hotkey with the bonus of the stake locked on it.
+//
+pub fn get_boosted_stake_for_hotkey(store: &dyn Storage, hotkey: &Addr) -> u64 {
+    let bonus = HOTKEY_LOCK_BONUS.may_load(store, hotkey).unwrap().unwrap_or_default();
+    get_total_stake_for_hotkey(store, hotkey).saturating_add(bonus)
+}
+
+// Removes the stake lock of the cold - hot pairing with its bonus.
+//
+pub fn remove_stake_lock(store: &mut dyn Storage, coldkey: &Addr, hotkey: &Addr) {
+    if let Some(lock) = STAKE_LOCKS.may_load(store, (hotkey, coldkey)).unwrap() {
+        STAKE_LOCKS.remove(store, (hotkey, coldkey));
+        let mut locks = STAKE_LOCKS_BY_UNLOCK
+            .may_load(store, lock.unlock_block)
+            .unwrap()
+            .unwrap_or_default();
+        locks.retain(|(h, c)| h != hotkey || c != coldkey);
+        if locks.is_empty() {
+            STAKE_LOCKS_BY_UNLOCK.remove(store, lock.unlock_block);
+        } else {
+            STAKE_LOCKS_BY_UNLOCK.save(store, lock.unlock_block, &locks).unwrap();
+        }
+        HOTKEY_LOCK_BONUS
+            .update(store, hotkey, |b| -> StdResult<_> {
+                Ok(b.unwrap_or_default().saturating_sub(lock.bonus))
+            })
+            .unwrap();
+    }
+}
+
+// Removes the stake locks which unlock at or before the block.
+//
+pub fn expire_stake_locks(store: &mut dyn Storage, block_number: u64) -> Result<(), ContractError> {
+    let expired = STAKE_LOCKS_BY_UNLOCK
+        .range(store.deref(), None, Some(Bound::inclusive(block_number)), Order::Ascending)
+        .collect::<StdResult<Vec<(u64, Vec<(Addr, Addr)>)>>>()?;
+    for (_, locks) in expired {
+        for (hotkey, coldkey) in locks {
+            remove_stake_lock(store, &coldkey, &hotkey);
+        }
+    }
+    Ok(())
 }
 
-// Increases the stake on the hotkey account under its owning coldkey.
+// ---- The implementation for the extrinsic claim: withdraws the claimable balance of the caller,
+// settling the paid out rewards on all staked hotkeys first.
+//
+// # Args:
+// 	* 'origin': (<T as frame_system::Config>RuntimeOrigin):
+// 		- The signature of the caller's coldkey.
 //
-pub fn increase_stake_on_hotkey_account(store: &mut dyn Storage, hotkey: &Addr, increment: u64) {
+// # Raises:
+// 	* 'NothingToClaim':
+// 		- The coldkey has no claimable balance.
+//
+pub fn do_claim(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let coldkey = info.sender;
+
+    // --- 1. Settle paid out rewards into the claimable balance.
+    let hotkeys = STAKED_HOTKEYS
+        .prefix(&coldkey)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    for hotkey in hotkeys {
+        if is_payout_preference(deps.storage, &coldkey, &hotkey) {
+            settle_nominator_rewards(deps.storage, &coldkey, &hotkey);
+        }
+    }
+
+    // --- 2. Ensure there is something to claim.
+    let amount = CLAIMABLE.may_load(deps.storage, &coldkey)?.unwrap_or_default();
+    ensure!(amount > 0, ContractError::NothingToClaim {});
+
+    // --- 3. Withdraw the balance, claimed tokens leave the issuance like unstaked ones
+    // except the part which was never issued.
+    let unissued = UNISSUED_CLAIMABLE.may_load(deps.storage, &coldkey)?.unwrap_or_default();
+    CLAIMABLE.remove(deps.storage, &coldkey);
+    UNISSUED_CLAIMABLE.remove(deps.storage, &coldkey);
+    let total_claimable = get_total_claimable(deps.storage);
+    TOTAL_CLAIMABLE.save(deps.storage, &total_claimable.saturating_sub(amount))?;
+    TOTAL_ISSUANCE.update(deps.storage, |s| -> StdResult<_> {
+        Ok(s.saturating_sub(amount.saturating_sub(unissued)))
+    })?;
+
+    let denom = DENOM.load(deps.storage)?;
+    let msg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: coldkey.to_string(),
+        amount: coins(Uint128::from(amount).u128(), denom),
+    });
+
+    deps.api.debug(&format!(
+        "🌐 Claimed ( coldkey:{:?}, amount:{:?} )",
+        coldkey, amount
+    ));
+
+    Ok(Response::default()
+        .add_message(msg)
+        .add_attribute("action", "claimed")
+        .add_attribute("coldkey", coldkey)
+        .add_attribute("amount", format!("{}", amount)))
+}
+
+// Returns true if the passed hotkey allow delegative staking.
+//
+pub fn hotkey_is_delegate(store: &dyn Storage, hotkey: &Addr) -> bool {
+    DELEGATES.has(store, hotkey)
+}
+
+// Sets the hotkey as a delegate with take.
+//
+pub fn delegate_hotkey(store: &mut dyn Storage, hotkey: &Addr, take: u16) {
+    if !hotkey_is_delegate(store, hotkey) {
+        let total_hotkey_stake = TOTAL_HOTKEY_STAKE
+            .may_load(store, hotkey)
+            .unwrap()
+            .unwrap_or_default();
+        DELEGATES_BY_STAKE
+            .save(store, (total_hotkey_stake, hotkey), &Empty {})
+            .unwrap();
+    }
+    DELEGATES.save(store, hotkey, &take).unwrap();
+}
+
+// Moves the delegate in the stake ordered index after its total stake changed.
+//
+fn update_delegates_by_stake(store: &mut dyn Storage, hotkey: &Addr, old_stake: u64, new_stake: u64) {
+    if !hotkey_is_delegate(store, hotkey) || old_stake == new_stake {
+        return;
+    }
+    DELEGATES_BY_STAKE.remove(store, (old_stake, hotkey));
+    DELEGATES_BY_STAKE
+        .save(store, (new_stake, hotkey), &Empty {})
+        .unwrap();
+}
+
+// Returns the total amount of stake in the staking table.
+#[cfg(test)]
+pub fn get_total_stake(store: &dyn Storage) -> u64 {
+    return TOTAL_STAKE.load(store).unwrap();
+}
+
+// Increases the total amount of stake by the passed amount.
+#[cfg(test)]
+pub fn increase_total_stake(store: &mut dyn Storage, increment: u64) {
+    TOTAL_STAKE
+        .update(store, |s| -> StdResult<_> {
+            Ok(s.saturating_add(increment))
+        })
+        .unwrap();
+}
+
+// Decreases the total amount of stake by the passed amount.
+#[cfg(test)]
+pub fn decrease_total_stake(store: &mut dyn Storage, decrement: u64) {
+    TOTAL_STAKE
+        .update(store, |s| -> StdResult<_> {
+            Ok(s.saturating_sub(decrement))
+        })
+        .unwrap();
+}
+
+// Returns the total amount of stake under a hotkey (delegative or otherwise)
+//
+pub fn get_total_stake_for_hotkey(store: &dyn Storage, hotkey: &Addr) -> u64 {
+    // TODO revisit and delete default
+    return TOTAL_HOTKEY_STAKE.load(store, hotkey).unwrap();
+}
+
+// Returns the total amount of stake held by the coldkey (delegative or otherwise)
+#[cfg(test)]
+pub fn get_total_stake_for_coldkey(store: &dyn Storage, coldkey: &Addr) -> u64 {
+    return TOTAL_COLDKEY_STAKE.load(store, coldkey).unwrap();
+}
+
+// Returns the stake under the cold - hot pairing in the staking table.
+//
+pub fn get_stake_for_coldkey_and_hotkey(store: &dyn Storage, coldkey: &Addr, hotkey: &Addr) -> u64 {
+    // Added default, see delegate_info:125
+    let stake = STAKE.load(store, (hotkey, coldkey)).unwrap_or_default();
+    if is_payout_preference(store, coldkey, hotkey) {
+        return stake;
+    }
+    stake.saturating_add(get_pending_rewards_for_coldkey_and_hotkey(store, coldkey, hotkey))
+}
+
+// Returns the nominator rewards accrued on the cold - hot pairing since its last settlement.
+//
+pub fn get_pending_rewards_for_coldkey_and_hotkey(
+    store: &dyn Storage,
+    coldkey: &Addr,
+    hotkey: &Addr,
+) -> u64 {
+    let stake = STAKE.may_load(store, (hotkey, coldkey)).unwrap().unwrap_or_default();
+    if stake == 0 {
+        return 0;
+    }
+    let reward_index = HOTKEY_REWARD_INDEX.may_load(store, hotkey).unwrap().unwrap_or_default();
+    let nominator_index = NOMINATOR_REWARD_INDEX
+        .may_load(store, (hotkey, coldkey))
+        .unwrap()
+        .unwrap_or_default();
+    let rewards = Uint128::from(stake) * (reward_index - nominator_index);
+
+    // Rounding never allows to settle more than was distributed.
+    let pending_rewards = if is_payout_preference(store, coldkey, hotkey) {
+        HOTKEY_PENDING_PAYOUTS.may_load(store, hotkey).unwrap().unwrap_or_default()
+    } else {
+        HOTKEY_PENDING_REWARDS.may_load(store, hotkey).unwrap().unwrap_or_default()
+    };
+    u64::try_from(rewards.u128()).unwrap_or(u64::MAX).min(pending_rewards)
+}
+
+// Returns true if the rewards of the cold - hot pairing are paid out instead of compounded.
+//
+pub fn is_payout_preference(store: &dyn Storage, coldkey: &Addr, hotkey: &Addr) -> bool {
+    REWARD_PREFERENCE.may_load(store, (hotkey, coldkey)).unwrap() == Some(RewardPreference::Payout)
+}
+
+// Returns the balance claimable by the coldkey, including not settled paid out rewards.
+//
+pub fn get_claimable_for_coldkey(store: &dyn Storage, coldkey: &Addr) -> u64 {
+    let claimable = CLAIMABLE.may_load(store, coldkey).unwrap().unwrap_or_default();
+    STAKED_HOTKEYS
+        .prefix(coldkey)
+        .keys(store, None, None, Order::Ascending)
+        .map(|hotkey| hotkey.unwrap())
+        .filter(|hotkey| is_payout_preference(store, coldkey, hotkey))
+        .fold(claimable, |claimable, hotkey| {
+            claimable.saturating_add(get_pending_rewards_for_coldkey_and_hotkey(store, coldkey, &hotkey))
+        })
+}
+
+// Returns the total claimable balance, zero for contracts migrated without the stored value.
+//
+pub fn get_total_claimable(store: &dyn Storage) -> u64 {
+    TOTAL_CLAIMABLE.may_load(store).unwrap().unwrap_or_default()
+}
+
+// Credits the claimable balance of the coldkey with tokens held by the contract.
+// Claimable tokens count in the total issuance until claimed.
+//
+pub fn add_claimable_balance(store: &mut dyn Storage, coldkey: &Addr, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    credit_claimable(store, coldkey, amount);
+    let total_claimable = get_total_claimable(store);
+    TOTAL_CLAIMABLE
+        .save(store, &total_claimable.saturating_add(amount))
+        .unwrap();
+    TOTAL_ISSUANCE
+        .update(store, |s| -> StdResult<_> { Ok(s.saturating_add(amount)) })
+        .unwrap();
+}
+
+// Credits the claimable balance of the coldkey with tokens held by the contract which
+// were never issued, like rewards sent to the contract. The total issuance is not changed.
+//
+pub fn add_unissued_claimable_balance(store: &mut dyn Storage, coldkey: &Addr, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    credit_claimable(store, coldkey, amount);
+    UNISSUED_CLAIMABLE
+        .update(store, coldkey, |u| -> StdResult<_> {
+            Ok(u.unwrap_or_default().saturating_add(amount))
+        })
+        .unwrap();
+    let total_claimable = get_total_claimable(store);
+    TOTAL_CLAIMABLE
+        .save(store, &total_claimable.saturating_add(amount))
+        .unwrap();
+}
+
+fn credit_claimable(store: &mut dyn Storage, coldkey: &Addr, amount: u64) {
+    CLAIMABLE
+        .update(store, coldkey, |c| -> StdResult<_> {
+            Ok(c.unwrap_or_default().saturating_add(amount))
+        })
+        .unwrap();
+}
+
+// Settles the nominator rewards accrued on the cold - hot pairing into its stake,
+// or into the claimable balance when rewards are paid out.
+// Must be called before the stake or the reward preference of the pairing changes.
+//
+pub fn settle_nominator_rewards(store: &mut dyn Storage, coldkey: &Addr, hotkey: &Addr) -> u64 {
+    let reward_index = HOTKEY_REWARD_INDEX.may_load(store, hotkey).unwrap().unwrap_or_default();
+    let nominator_index = NOMINATOR_REWARD_INDEX
+        .may_load(store, (hotkey, coldkey))
+        .unwrap()
+        .unwrap_or_default();
+    if nominator_index == reward_index {
+        return 0;
+    }
+
+    let rewards = get_pending_rewards_for_coldkey_and_hotkey(store, coldkey, hotkey);
+    if rewards > 0 && is_payout_preference(store, coldkey, hotkey) {
+        ACCRUED_REWARDS
+            .update(store, (hotkey, coldkey), |r| -> StdResult<_> {
+                Ok(r.unwrap_or_default().saturating_add(rewards))
+            })
+            .unwrap();
+        // Paid out rewards are already counted in the total claimable on distribution.
+        credit_claimable(store, coldkey, rewards);
+        HOTKEY_PENDING_PAYOUTS
+            .update(store, hotkey, |s| -> StdResult<_> {
+                Ok(s.unwrap_or_default().saturating_sub(rewards))
+            })
+            .unwrap();
+    } else if rewards > 0 {
+        ACCRUED_REWARDS
+            .update(store, (hotkey, coldkey), |r| -> StdResult<_> {
+                Ok(r.unwrap_or_default().saturating_add(rewards))
+            })
+            .unwrap();
+        // Rewards are already counted in the hotkey and total stake on distribution.
+        STAKE
+            .update(store, (hotkey, coldkey), |s| -> StdResult<_> {
+                Ok(s.unwrap_or_default().saturating_add(rewards))
+            })
+            .unwrap();
+        TOTAL_COLDKEY_STAKE
+            .update(store, coldkey, |s| -> StdResult<_> {
+                Ok(s.unwrap_or_default().saturating_add(rewards))
+            })
+            .unwrap();
+        HOTKEY_PENDING_REWARDS
+            .update(store, hotkey, |s| -> StdResult<_> {
+                Ok(s.unwrap_or_default().saturating_sub(rewards))
+            })
+            .unwrap();
+    }
+    NOMINATOR_REWARD_INDEX
+        .save(store, (hotkey, coldkey), &reward_index)
+        .unwrap();
+
+    rewards
+}
+
+// Returns the rewards credited to the cold - hot pairing, including not settled rewards.
+//
+pub fn get_accrued_rewards_for_coldkey_and_hotkey(
+    store: &dyn Storage,
+    coldkey: &Addr,
+    hotkey: &Addr,
+) -> u64 {
+    let rewards = ACCRUED_REWARDS.may_load(store, (hotkey, coldkey)).unwrap().unwrap_or_default();
+    rewards.saturating_add(get_pending_rewards_for_coldkey_and_hotkey(store, coldkey, hotkey))
+}
+
+// Credits the rewards to the stake of the hotkey owning coldkey, or to its claimable balance
+// when the owner has rewards paid out.
+//
+pub fn credit_rewards_on_hotkey_account(store: &mut dyn Storage, hotkey: &Addr, rewards: u64) {
+    if rewards == 0 {
+        return;
+    }
     let coldkey = get_owning_coldkey_for_hotkey(store, hotkey);
-    increase_stake_on_coldkey_hotkey_account(store, &coldkey, hotkey, increment);
+    if is_payout_preference(store, &coldkey, hotkey) {
+        add_claimable_balance(store, &coldkey, rewards);
+    } else {
+        increase_stake_on_coldkey_hotkey_account(store, &coldkey, hotkey, rewards);
+    }
+    ACCRUED_REWARDS
+        .update(store, (hotkey, &coldkey), |r| -> StdResult<_> {
+            Ok(r.unwrap_or_default().saturating_add(rewards))
+        })
+        .unwrap();
+}
+
+// Removes the rewards history of the delegate older than REWARDS_HISTORY_DAYS before the given day.
+//
+fn prune_rewards_history(store: &mut dyn Storage, hotkey: &Addr, day: u64) {
+    let expired_before = (day + 1).saturating_sub(REWARDS_HISTORY_DAYS);
+    let expired_days = HOTKEY_REWARDS_HISTORY
+        .prefix(hotkey)
+        .keys(store, None, Some(Bound::exclusive(expired_before)), Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()
+        .unwrap();
+    for expired_day in expired_days {
+        HOTKEY_REWARDS_HISTORY.remove(store, (hotkey, expired_day));
+    }
+}
+
+// Distributes the emission to the nominators of the delegate in proportion to their stake
+// by increasing the delegate reward index. Nominators settle their rewards lazily.
+// Returns the distributed amount, nothing is distributed when the delegate has no stake.
+//
+pub fn distribute_rewards_to_nominators(
+    store: &mut dyn Storage,
+    hotkey: &Addr,
+    emission: u64,
+    block_number: u64,
+) -> u64 {
+    let total_hotkey_stake = TOTAL_HOTKEY_STAKE.may_load(store, hotkey).unwrap().unwrap_or_default();
+    let pending_rewards = HOTKEY_PENDING_REWARDS.may_load(store, hotkey).unwrap().unwrap_or_default();
+    // Pending rewards are not settled into stake so they don't earn until settlement.
+    let nominators_stake = total_hotkey_stake.saturating_sub(pending_rewards);
+    if emission == 0 || nominators_stake == 0 {
+        return 0;
+    }
+
+    // Share of nominators with rewards paid out doesn't add to the stake.
+    let payout_stake = HOTKEY_PAYOUT_STAKE.may_load(store, hotkey).unwrap().unwrap_or_default();
+    let payout = (emission as u128 * payout_stake.min(nominators_stake) as u128
+        / nominators_stake as u128) as u64;
+    let compound = emission - payout;
+
+    let reward_per_share = Decimal::from_ratio(emission, nominators_stake);
+    HOTKEY_REWARD_INDEX
+        .update(store, hotkey, |i| -> StdResult<_> {
+            Ok(i.unwrap_or_default() + reward_per_share)
+        })
+        .unwrap();
+    let day = block_number / get_blocks_per_day(store);
+    if !HOTKEY_REWARDS_HISTORY.has(store, (hotkey, day)) {
+        prune_rewards_history(store, hotkey, day);
+    }
+    HOTKEY_REWARDS_HISTORY
+        .update(store, (hotkey, day), |r| -> StdResult<_> {
+            Ok(r.unwrap_or_default() + reward_per_share)
+        })
+        .unwrap();
+    HOTKEY_PENDING_REWARDS
+        .save(store, hotkey, &pending_rewards.saturating_add(compound))
+        .unwrap();
+    TOTAL_HOTKEY_STAKE
+        .save(store, hotkey, &total_hotkey_stake.saturating_add(compound))
+        .unwrap();
+    update_delegates_by_stake(
+        store,
+        hotkey,
+        total_hotkey_stake,
+        total_hotkey_stake.saturating_add(compound),
+    );
+    TOTAL_STAKE
+        .update(store, |s| -> StdResult<_> {
+            Ok(s.saturating_add(compound))
+        })
+        .unwrap();
+    if payout > 0 {
+        HOTKEY_PENDING_PAYOUTS
+            .update(store, hotkey, |p| -> StdResult<_> {
+                Ok(p.unwrap_or_default().saturating_add(payout))
+            })
+            .unwrap();
+        let total_claimable = get_total_claimable(store);
+        TOTAL_CLAIMABLE
+            .save(store, &total_claimable.saturating_add(payout))
+            .unwrap();
+    }
+    TOTAL_ISSUANCE
+        .update(store, |s| -> StdResult<_> {
+            Ok(s.saturating_add(emission))
+        })
+        .unwrap();
+
+    emission
+}
+
+// Creates a cold - hot pairing account if the hotkey is not already an active account.
+//
+pub fn create_account_if_non_existent(store: &mut dyn Storage, coldkey: &Addr, hotkey: &Addr) {
+    if !hotkey_account_exists(store, hotkey) {
+        STAKE.save(store, (hotkey, coldkey), &0).unwrap();
+        OWNER.save(store, hotkey, coldkey).unwrap();
+        TOTAL_HOTKEY_STAKE.save(store, hotkey, &0u64).unwrap();
+        TOTAL_COLDKEY_STAKE.save(store, coldkey, &0u64).unwrap();
+        HOTKEY_OWNER_APPROVALS.remove(store, hotkey);
+    }
+}
+
+// Checks that the coldkey may create the hotkey account. New hotkeys must approve the coldkey
+// first unless the hotkey registers itself.
+//
+pub fn ensure_hotkey_owner_approved(
+    store: &dyn Storage,
+    coldkey: &Addr,
+    hotkey: &Addr,
+) -> Result<(), ContractError> {
+    if hotkey_account_exists(store, hotkey) || coldkey == hotkey {
+        return Ok(());
+    }
+    let approved = HOTKEY_OWNER_APPROVALS.may_load(store, hotkey)?;
+    ensure!(
+        approved.as_ref() == Some(coldkey),
+        ContractError::HotkeyOwnershipNotApproved {}
+    );
+    Ok(())
+}
+
+// Returns the coldkey owning this hotkey. This function should only be called for active accounts.
+//
+pub fn get_owning_coldkey_for_hotkey(store: &dyn Storage, hotkey: &Addr) -> Addr {
+    return OWNER.load(store, hotkey).unwrap();
+}
+
+pub fn hotkey_account_exists(store: &dyn Storage, hotkey: &Addr) -> bool {
+    return OWNER.has(store, hotkey);
+}
+
+// Return true if the passed coldkey owns the hotkey.
+//
+pub fn coldkey_owns_hotkey(store: &dyn Storage, coldkey: &Addr, hotkey: &Addr) -> bool {
+    if OWNER.has(store, hotkey) {
+        return OWNER.load(store, hotkey).unwrap().eq(coldkey);
+    } else {
+        return false;
+    }
+}
+
+// Returns true if the cold-hot staking account has enough balance to fufil the decrement.
+//
+pub fn has_enough_stake(
+    store: &dyn Storage,
+    coldkey: &Addr,
+    hotkey: &Addr,
+    decrement: u64,
+) -> bool {
+    return get_stake_for_coldkey_and_hotkey(store, coldkey, hotkey) >= decrement;
 }
 
 // Decreases the stake on the hotkey account under its owning coldkey.
@@ -509,24 +1639,44 @@ pub fn increase_stake_on_coldkey_hotkey_account(
     hotkey: &Addr,
     increment: u64,
 ) {
+    settle_nominator_rewards(store, coldkey, hotkey);
     TOTAL_COLDKEY_STAKE
         .update(store, coldkey, |s| -> StdResult<_> {
             let stake = s.unwrap_or_default();
             Ok(stake.saturating_add(increment))
         })
         .unwrap();
+    let total_hotkey_stake = TOTAL_HOTKEY_STAKE
+        .may_load(store, hotkey)
+        .unwrap()
+        .unwrap_or_default();
     TOTAL_HOTKEY_STAKE
-        .update(store, hotkey, |s| -> StdResult<_> {
-            let stake = s.unwrap_or_default();
-            Ok(stake.saturating_add(increment))
-        })
+        .save(store, hotkey, &total_hotkey_stake.saturating_add(increment))
         .unwrap();
-    STAKE
+    update_delegates_by_stake(
+        store,
+        hotkey,
+        total_hotkey_stake,
+        total_hotkey_stake.saturating_add(increment),
+    );
+    let stake = STAKE
         .update(store, (hotkey, coldkey), |s| -> StdResult<_> {
             let stake = s.unwrap_or_default();
             Ok(stake.saturating_add(increment))
         })
         .unwrap();
+    if is_payout_preference(store, coldkey, hotkey) {
+        HOTKEY_PAYOUT_STAKE
+            .update(store, hotkey, |s| -> StdResult<_> {
+                Ok(s.unwrap_or_default().saturating_add(increment))
+            })
+            .unwrap();
+    }
+    if stake > 0 && !STAKED_HOTKEYS.has(store, (coldkey, hotkey)) {
+        STAKED_HOTKEYS
+            .save(store, (coldkey, hotkey), &Empty {})
+            .unwrap();
+    }
     TOTAL_STAKE
         .update(store, |s| -> StdResult<_> {
             Ok(s.saturating_add(increment))
@@ -547,18 +1697,35 @@ pub fn decrease_stake_on_coldkey_hotkey_account(
     hotkey: &Addr,
     decrement: u64,
 ) -> Result<(), ContractError> {
+    settle_nominator_rewards(store, coldkey, hotkey);
     TOTAL_COLDKEY_STAKE.update(store, coldkey, |s| -> StdResult<_> {
         let stake = s.unwrap();
         Ok(stake.saturating_sub(decrement))
     })?;
-    TOTAL_HOTKEY_STAKE.update(store, hotkey, |s| -> StdResult<_> {
-        let stake = s.unwrap();
-        Ok(stake.saturating_sub(decrement))
-    })?;
-    STAKE.update(store, (hotkey, coldkey), |s| -> StdResult<_> {
+    let total_hotkey_stake = TOTAL_HOTKEY_STAKE.load(store, hotkey)?;
+    TOTAL_HOTKEY_STAKE.save(store, hotkey, &total_hotkey_stake.saturating_sub(decrement))?;
+    update_delegates_by_stake(
+        store,
+        hotkey,
+        total_hotkey_stake,
+        total_hotkey_stake.saturating_sub(decrement),
+    );
+    let mut decreased: u64 = 0;
+    let stake = STAKE.update(store, (hotkey, coldkey), |s| -> StdResult<_> {
         let stake = s.unwrap();
+        decreased = stake.min(decrement);
         Ok(stake.saturating_sub(decrement))
     })?;
+    if is_payout_preference(store, coldkey, hotkey) {
+        HOTKEY_PAYOUT_STAKE.update(store, hotkey, |s| -> StdResult<_> {
+            Ok(s.unwrap_or_default().saturating_sub(decreased))
+        })?;
+    }
+    if stake == 0 {
+        STAKED_HOTKEYS.remove(store, (coldkey, hotkey));
+        NOMINATOR_REWARD_INDEX.remove(store, (hotkey, coldkey));
+        remove_stake_lock(store, coldkey, hotkey);
+    }
     TOTAL_STAKE.update(store, |s| -> StdResult<_> {
         Ok(s.saturating_sub(decrement))
     })?;
@@ -584,20 +1751,21 @@ pub fn decrease_stake_on_coldkey_hotkey_account(
 //     true
 // }
 
+// Unstakes all coldkeys from the hotkey account, the stake is credited to their claimable balances.
+//
 pub fn unstake_all_coldkeys_from_hotkey_account(
     store: &mut dyn Storage,
     hotkey: &Addr,
-) -> Result<Vec<CosmosMsg>, ContractError> {
-    // TODO can be issue when there are a lot of stakers (and messages) on account on replacement
+) -> Result<(), ContractError> {
+    // TODO can be issue when there are a lot of stakers on account on replacement
     // Iterate through all coldkeys that have a stake on this hotkey account.
-    let mut msgs: Vec<CosmosMsg> = Vec::new();
-
     let stakes = STAKE
         .prefix(hotkey)
         .range(store.deref(), None, None, Order::Ascending)
         .map(|item| {
-            let i = item.unwrap();
-            (i.0, i.1)
+            let (coldkey, _) = item.unwrap();
+            let stake = get_stake_for_coldkey_and_hotkey(store.deref(), &coldkey, hotkey);
+            (coldkey, stake)
         })
         .collect::<Vec<(Addr, u64)>>();
 
@@ -609,14 +1777,9 @@ pub fn unstake_all_coldkeys_from_hotkey_account(
 
             // Remove the stake from the coldkey - hotkey pairing.
             decrease_stake_on_coldkey_hotkey_account(store, &delegate_coldkey_i, &hotkey, stake_i)?;
-
-            let denom = DENOM.load(store)?;
-            msgs.push(CosmosMsg::Bank(BankMsg::Send {
-                to_address: delegate_coldkey_i.to_string(),
-                amount: coins(Uint128::from(stake_i).u128(), denom),
-            }));
+            add_claimable_balance(store, &delegate_coldkey_i, stake_i);
         }
     }
 
-    Ok(msgs)
+    Ok(())
 }
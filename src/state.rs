@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -11,6 +11,8 @@ pub const COMMISSION_CHANGE: Item<bool> = Item::new("commission_switch");
 pub const DENOM: Item<String> = Item::new("denom");
 pub const VERSE_METADATA: Item<Metadata> = Item::new("verse_metadata");
 pub const TOTAL_REWARDS: Item<u64> = Item::new("total_rewards");
+// Used for returns estimations, depends on the chain block time
+pub const BLOCKS_PER_DAY: Item<u64> = Item::new("blocks_per_day");
 
 // ============================
 // ==== Staking + Accounts ====
@@ -32,11 +34,82 @@ pub const TOTAL_HOTKEY_STAKE: Map<&Addr, u64> = Map::new("total_hotkey_stake");
 pub const TOTAL_COLDKEY_STAKE: Map<&Addr, u64> = Map::new("total_coldkey_stake");
 // --- MAP ( hot ) --> cold | Returns the controlling coldkey for a hotkey.
 pub const OWNER: Map<&Addr, Addr> = Map::new("hotkey_coldkey");
+// --- MAP ( hot ) --> cold | Coldkey approved by the hotkey to claim its ownership on registration.
+pub const HOTKEY_OWNER_APPROVALS: Map<&Addr, Addr> = Map::new("hotkey_owner_approvals");
 // --- MAP ( hot ) --> stake take | Returns the hotkey delegation stake take(commission). And signals that this key is open for delegation.
 // TODO rename storage name
 pub const DELEGATES: Map<&Addr, u16> = Map::new("hotkey_stake");
 // --- DMAP ( hot, cold ) --> stake | Returns the stake under a coldkey prefixed by hotkey.
 pub const STAKE: Map<(&Addr, &Addr), u64> = Map::new("staked_hotkey_coldkey");
+// --- MAP ( hot ) --> reward_index | Cumulative nominators reward per unit of stake of a delegate.
+pub const HOTKEY_REWARD_INDEX: Map<&Addr, Decimal> = Map::new("hotkey_reward_index");
+// --- MAP ( hot ) --> rewards | Nominators rewards distributed by a delegate and not settled yet.
+pub const HOTKEY_PENDING_REWARDS: Map<&Addr, u64> = Map::new("hotkey_pending_rewards");
+// --- DMAP ( hot, cold ) --> reward_index | Delegate reward index at the last settlement of a nominator.
+pub const NOMINATOR_REWARD_INDEX: Map<(&Addr, &Addr), Decimal> = Map::new("nominator_reward_index");
+// --- DMAP ( hot, cold ) --> rewards | Cumulative rewards credited to the stake of a coldkey on a hotkey.
+pub const ACCRUED_REWARDS: Map<(&Addr, &Addr), u64> = Map::new("accrued_rewards");
+// --- DMAP ( hot, cold ) --> preference | How rewards of a coldkey on a hotkey are credited, compound when absent.
+pub const REWARD_PREFERENCE: Map<(&Addr, &Addr), RewardPreference> = Map::new("reward_preference");
+// --- MAP ( hot ) --> stake | Settled stake of the nominators of a delegate which have rewards paid out.
+pub const HOTKEY_PAYOUT_STAKE: Map<&Addr, u64> = Map::new("hotkey_payout_stake");
+// --- MAP ( hot ) --> rewards | Paid out rewards distributed by a delegate and not settled yet.
+pub const HOTKEY_PENDING_PAYOUTS: Map<&Addr, u64> = Map::new("hotkey_pending_payouts");
+// --- MAP ( cold ) --> amount | Balance a coldkey can claim from the contract.
+pub const CLAIMABLE: Map<&Addr, u64> = Map::new("claimable");
+// --- ITEM ( total_claimable ) | Claimable balances including not settled paid out rewards.
+pub const TOTAL_CLAIMABLE: Item<u64> = Item::new("total_claimable");
+// --- MAP ( cold ) --> amount | Part of the claimable balance never counted in the total issuance.
+pub const UNISSUED_CLAIMABLE: Map<&Addr, u64> = Map::new("unissued_claimable");
+// --- DMAP ( hot, day ) --> reward_per_share | Nominators reward per unit of stake distributed by a delegate per day, kept for a year.
+pub const HOTKEY_REWARDS_HISTORY: Map<(&Addr, u64), Decimal> = Map::new("hotkey_rewards_history");
+// --- DMAP ( stake, hot ) --> () | Index of delegates ordered by their total stake.
+pub const DELEGATES_BY_STAKE: Map<(u64, &Addr), Empty> = Map::new("delegates_by_stake");
+// --- DMAP ( cold, hot ) --> () | Index of hotkeys a coldkey has stake on.
+pub const STAKED_HOTKEYS: Map<(&Addr, &Addr), Empty> = Map::new("staked_coldkey_hotkey");
+// --- DMAP ( hot, cold ) --> lock | Stake of a coldkey on a hotkey locked until the unlock block.
+pub const STAKE_LOCKS: Map<(&Addr, &Addr), StakeLock> = Map::new("stake_locks");
+// --- MAP ( unlock_block ) --> Vec<( hot, cold )> | Index of stake locks by their unlock block.
+pub const STAKE_LOCKS_BY_UNLOCK: Map<u64, Vec<(Addr, Addr)>> = Map::new("stake_locks_by_unlock");
+// --- MAP ( hot ) --> bonus | Extra stake counted for a hotkey from the stake locked on it.
+pub const HOTKEY_LOCK_BONUS: Map<&Addr, u64> = Map::new("hotkey_lock_bonus");
+// --- ITEM ( max_lock_duration ) | Longest duration stake can be locked for, in blocks.
+pub const MAX_LOCK_DURATION: Item<u64> = Item::new("max_lock_duration");
+// --- ITEM ( max_lock_bonus ) | Bonus of stake locked for the max duration, u16::MAX is 100%.
+pub const MAX_LOCK_BONUS: Item<u16> = Item::new("max_lock_bonus");
+// --- MAP ( hot ) --> limits | Capacity and openness of a delegate to nominators, unlimited when absent.
+pub const DELEGATE_LIMITS: Map<&Addr, DelegateLimits> = Map::new("delegate_limits");
+// --- MAP ( hot ) --> identity | Returns the identity published by the delegate owner.
+pub const DELEGATE_IDENTITY: Map<&Addr, DelegateIdentity> = Map::new("delegate_identity");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct DelegateLimits {
+    pub max_delegated_stake: Option<u64>, // --- Max stake of nominators other than the owner.
+    pub closed: bool,                     // --- Closed to new nominators, existing ones keep their stake.
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakeLock {
+    pub amount: u64,       // --- Locked stake.
+    pub unlock_block: u64, // --- Block the stake can be removed from.
+    pub bonus: u64,        // --- Extra stake counted for the hotkey while locked.
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RewardPreference {
+    Compound,
+    Payout,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegateIdentity {
+    pub name: String,
+    pub url: String,
+    pub description: String, // particle
+    pub image: String, // particle
+    pub contact: String,
+}
 
 // =====================================
 // ==== Difficulty / Registrations =====
@@ -44,6 +117,10 @@ pub const STAKE: Map<(&Addr, &Addr), u64> = Map::new("staked_hotkey_coldkey");
 
 // ---- StorageItem Global Used Work.
 pub const USED_WORK: Map<Vec<u8>, u64> = Map::new("global_used_work");
+// --- ITEM ( work ) | Last used work checked by the incremental pruning, scan restarts when absent.
+pub const USED_WORK_PRUNE_CURSOR: Item<Vec<u8>> = Item::new("used_work_prune_cursor");
+// --- MAP ( block ) --> seed | Seed of the block which PoW registration work commits to, kept for recent blocks.
+pub const BLOCK_SEEDS: Map<u64, Vec<u8>> = Map::new("block_seeds");
 // --- MAP ( netuid ) --> Burn
 pub const BURN: Map<u16, u64> = Map::new("burn");
 // --- MAP ( netuid ) --> Difficulty
@@ -64,6 +141,34 @@ pub const REGISTRATIONS_THIS_BLOCK: Map<u16, u16> = Map::new("registrations_this
 pub const MAX_REGISTRATION_PER_BLOCK: Map<u16, u16> = Map::new("max_registration_per_block");
 // --- MAP ( netuid ) --> global_RAO_recycled_for_registration )
 pub const RAO_RECYCLED_FOR_REGISTRATION: Map<u16, u64> = Map::new("rao_recycled_for_registration");
+// --- MAP ( netuid ) --> mode | Registration paths open on the subnet, PoW and burn when absent.
+pub const REGISTRATION_MODE: Map<u16, RegistrationMode> = Map::new("registration_mode");
+// --- MAP ( netuid ) --> stake | Stake locked on the new hotkey for the immunity period in the stake registration mode.
+pub const REGISTRATION_STAKE: Map<u16, u64> = Map::new("registration_stake");
+// --- MAP ( netuid ) --> enabled | Only allowlisted hotkeys or coldkeys register on the subnet when enabled.
+pub const REGISTRATION_ALLOWLIST_ENABLED: Map<u16, bool> = Map::new("registration_allowlist_enabled");
+// --- DMAP ( netuid, key ) --> () | Hotkeys and coldkeys allowed by the subnet owner to register.
+pub const REGISTRATION_ALLOWLIST: Map<(u16, &Addr), Empty> = Map::new("registration_allowlist");
+// --- MAP ( netuid ) --> destination | Where the burned registration fees go, burned when absent.
+pub const REGISTRATION_FEE_DESTINATION: Map<u16, RegistrationFeeDestination> = Map::new("registration_fee_destination");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationMode {
+    Pow,          // --- Only PoW registrations.
+    Burn,         // --- Only burned registrations.
+    Both,         // --- PoW and burned registrations.
+    Permissioned, // --- Only registrations by the subnet owner.
+    Stake,        // --- Only registrations locking the registration stake on the new hotkey.
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationFeeDestination {
+    Burn,        // --- Burned through the bank module.
+    Recycle,     // --- Kept in the contract as reserve for future emission.
+    SubnetOwner, // --- Sent to the subnet owner.
+}
 
 // ==============================
 // ==== Subnetworks Storage =====
@@ -73,6 +178,10 @@ pub const RAO_RECYCLED_FOR_REGISTRATION: Map<u16, u64> = Map::new("rao_recycled_
 pub const SUBNET_LIMIT: Item<u16> = Item::new("subnet_limit");
 // --- ITEM( total_number_of_existing_networks )
 pub const TOTAL_NETWORKS: Item<u16> = Item::new("total_networks");
+// --- ITEM ( root_min_stake ) | Total stake a hotkey needs to join and stay on the root network.
+pub const ROOT_MIN_STAKE: Item<u64> = Item::new("root_min_stake");
+// --- ITEM ( root_auto_delegate ) | Root registration makes the hotkey a delegate, enabled when absent.
+pub const ROOT_AUTO_DELEGATE: Item<bool> = Item::new("root_auto_delegate");
 // --- MAP ( netuid ) --> subnetwork_n (Number of UIDs in the network).
 pub const SUBNETWORK_N: Map<u16, u16> = Map::new("subnetwork_n");
 // --- MAP ( netuid ) --> modality   TEXT: 0, IMAGE: 1, TENSOR: 2
@@ -171,6 +280,8 @@ pub const TX_RATE_LIMIT: Item<u64> = Item::new("tx_rate_limit");
 pub const LAST_TX_BLOCK: Map<&Addr, u64> = Map::new("last_tx_block");
 // --- MAP ( netuid ) --> serving_rate_limit
 pub const SERVING_RATE_LIMIT: Map<u16, u64> = Map::new("serving_rate_limit");
+// --- MAP ( netuid ) --> allowed | Private and reserved ips accepted in serving, for private test networks.
+pub const SERVING_ALLOW_PRIVATE_IPS: Map<u16, bool> = Map::new("serving_allow_private_ips");
 // --- MAP ( netuid, hotkey ) --> axon_info
 pub const AXONS: Map<(u16, &Addr), AxonInfo> = Map::new("axon_info");
 // --- MAP ( netuid, hotkey ) --> prometheus_info
@@ -257,9 +368,35 @@ pub const VALIDATOR_TRUST: Map<u16, Vec<u16>> = Map::new("validator_trust");
 pub const PRUNING_SCORES: Map<u16, Vec<u16>> = Map::new("pruning_scores");
 // --- MAP ( netuid ) --> validator_permit
 pub const VALIDATOR_PERMIT: Map<u16, Vec<bool>> = Map::new("validator_permit");
+// --- MAP ( netuid ) --> slashing | Opt-in slashing of low trust validators, disabled when absent.
+pub const SLASHING: Map<u16, SlashingConfig> = Map::new("slashing");
+// --- DMAP ( netuid, uid ) --> epochs | Consecutive epochs with validator trust below the slashing threshold.
+pub const LOW_TRUST_EPOCHS: Map<(u16, u16), u16> = Map::new("low_trust_epochs");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SlashingConfig {
+    pub trust_threshold: u16,        // --- Validator trust below which the epoch counts as low trust.
+    pub epochs: u16,                 // --- Consecutive low trust epochs before slashing.
+    pub fraction: u16,               // --- Slashed fraction of stake, out of u16::MAX.
+    pub share_with_nominators: bool, // --- Slash nominators proportionally instead of the owner only.
+    pub treasury: Option<Addr>,      // --- Receiver of slashed tokens, burned when not set.
+}
 // --- DMAP ( netuid, uid ) --> weights
 pub const WEIGHTS: Map<(u16, u16), Vec<(u16, u16)>> = Map::new("weights");
 // --- DMAP ( netuid, uid ) --> bonds
 pub const BONDS: Map<(u16, u16), Vec<(u16, u16)>> = Map::new("bonds");
 
 pub const ALLOW_FAUCET: Item<bool> = Item::new("allow_faucet");
+// --- ITEM ( faucet_config ) | Payout, cooldown and PoW difficulty of the faucet, defaults when absent.
+pub const FAUCET_CONFIG: Item<FaucetConfig> = Item::new("faucet_config");
+// --- ITEM ( faucet_reserve ) | Tokens funded by root which the faucet pays out from.
+pub const FAUCET_RESERVE: Item<u64> = Item::new("faucet_reserve");
+// --- MAP ( cold ) --> block | Block of the last faucet payout to the coldkey.
+pub const FAUCET_LAST_PAYOUT: Map<&Addr, u64> = Map::new("faucet_last_payout");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FaucetConfig {
+    pub amount: u64,     // --- Tokens paid out per faucet call.
+    pub cooldown: u64,   // --- Blocks a coldkey waits between payouts.
+    pub difficulty: u64, // --- PoW difficulty of the faucet work.
+}
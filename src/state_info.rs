@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Order, StdResult, Storage};
 
+use crate::staking::get_stake_for_coldkey_and_hotkey;
 use crate::state::{
     AxonInfo, PrometheusInfo, ACTIVE, ACTIVITY_CUTOFF, ADJUSTMENTS_ALPHA, ADJUSTMENT_INTERVAL,
     ALLOW_FAUCET, AXONS, BLOCKS_SINCE_LAST_STEP, BLOCK_AT_REGISTRATION, BLOCK_EMISSION, BONDS,
@@ -134,7 +135,12 @@ pub fn get_state_info(store: &dyn Storage) -> StdResult<StateInfo> {
         .collect::<StdResult<Vec<_>>>()
         .unwrap();
     let staked_hotkey_coldkey: Vec<((Addr, Addr), u64)> = STAKE
-        .range(store, None, None, Order::Ascending)
+        .keys(store, None, None, Order::Ascending)
+        .map(|item| {
+            let (hotkey, coldkey) = item?;
+            let stake = get_stake_for_coldkey_and_hotkey(store, &coldkey, &hotkey);
+            Ok(((hotkey, coldkey), stake))
+        })
         .collect::<StdResult<Vec<_>>>()
         .unwrap();
     let global_used_work: Vec<(Vec<u8>, u64)> = USED_WORK
@@ -3,7 +3,7 @@ use cosmwasm_std::{Addr, Deps, Order, StdResult};
 
 use crate::root::if_subnet_exist;
 use crate::state::{
-    Metadata, ACTIVITY_CUTOFF, ADJUSTMENT_INTERVAL, BLOCKS_SINCE_LAST_STEP, BONDS_MOVING_AVERAGE, BURN,
+    Metadata, RegistrationMode, ACTIVITY_CUTOFF, ADJUSTMENT_INTERVAL, BLOCKS_SINCE_LAST_STEP, BONDS_MOVING_AVERAGE, BURN,
     DIFFICULTY, EMISSION_VALUES, IMMUNITY_PERIOD, KAPPA, MAX_ALLOWED_UIDS, MAX_ALLOWED_VALIDATORS,
     MAX_BURN, MAX_DIFFICULTY, MAX_REGISTRATION_PER_BLOCK, MAX_WEIGHTS_LIMIT, NETWORKS_METADATA,
     MIN_ALLOWED_WEIGHTS, MIN_BURN, MIN_DIFFICULTY, NETWORKS_ADDED, NETWORK_MODALITY,
@@ -11,6 +11,7 @@ use crate::state::{
     WEIGHTS_SET_RATE_LIMIT, WEIGHTS_VERSION_KEY,
 };
 use crate::uids::get_subnetwork_n;
+use crate::utils::{get_registration_mode, get_registration_stake, get_serving_allow_private_ips};
 
 #[cw_serde]
 pub struct SubnetInfo {
@@ -48,6 +49,9 @@ pub struct SubnetHyperparams {
     pub adjustment_interval: u16,
     pub activity_cutoff: u16,
     pub registration_allowed: bool,
+    pub registration_mode: RegistrationMode,
+    pub registration_stake: u64,
+    pub serving_allow_private_ips: bool,
     pub target_regs_per_interval: u16,
     pub min_burn: u64,
     pub max_burn: u64,
@@ -160,6 +164,9 @@ pub fn get_subnet_hyperparams(deps: Deps, netuid: u16) -> StdResult<Option<Subne
         adjustment_interval: adjustment_interval.into(),
         activity_cutoff: activity_cutoff.into(),
         registration_allowed,
+        registration_mode: get_registration_mode(deps.storage, netuid),
+        registration_stake: get_registration_stake(deps.storage, netuid),
+        serving_allow_private_ips: get_serving_allow_private_ips(deps.storage, netuid),
         target_regs_per_interval: target_regs_per_interval.into(),
         min_burn: min_burn.into(),
         max_burn: max_burn.into(),
@@ -14,7 +14,8 @@ use cyber_std_test::CyberApp;
 
 use crate::contract::{execute, instantiate, query};
 use crate::msg::ExecuteMsg;
-use crate::registration::create_work_for_block_number;
+use crate::registration::{create_work_for_block_number, record_block_seed};
+use crate::state::OWNER;
 use crate::root::{get_network_lock_cost, init_new_network};
 use crate::utils::{
     get_burn_as_u64, get_difficulty_as_u64, set_difficulty, set_network_registration_allowed,
@@ -161,13 +162,15 @@ pub fn register_ok_neuron_app(
 }
 
 pub fn register_ok_neuron(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     netuid: u16,
     hotkey: &str,
     coldkey: &str,
     start_nonce: u64,
 ) -> Result<Response, ContractError> {
+    // The block step records the seed of every block on chain, tests may skip it.
+    record_block_seed(deps.storage, &env).unwrap();
     let (nonce, work): (u64, Vec<u8>) = create_work_for_block_number(
         deps.as_ref().storage,
         netuid,
@@ -215,7 +218,15 @@ pub fn pow_register_ok_neuron(
     result
 }
 
-pub fn sudo_register_ok_neuron(deps: DepsMut, _env: Env, netuid: u16, hotkey: &str, coldkey: &str) {
+pub fn sudo_register_ok_neuron(
+    mut deps: DepsMut,
+    _env: Env,
+    netuid: u16,
+    hotkey: &str,
+    coldkey: &str,
+) {
+    approve_hotkey_owner(deps.branch(), mock_env(), hotkey, coldkey);
+
     let msg = ExecuteMsg::SudoRegister {
         netuid,
         hotkey: hotkey.to_string(),
@@ -229,12 +240,27 @@ pub fn sudo_register_ok_neuron(deps: DepsMut, _env: Env, netuid: u16, hotkey: &s
     assert_eq!(res.is_ok(), true);
 }
 
+pub fn approve_hotkey_owner(deps: DepsMut, env: Env, hotkey: &str, coldkey: &str) {
+    if hotkey == coldkey || OWNER.has(deps.storage, &Addr::unchecked(hotkey)) {
+        return;
+    }
+    let msg = ExecuteMsg::ApproveHotkeyOwner {
+        coldkey: coldkey.to_string(),
+    };
+
+    let info = mock_info(hotkey, &[]);
+    let res = execute(deps, env, info, msg);
+    assert_eq!(res.is_ok(), true);
+}
+
 pub fn root_register(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     hotkey: &str,
     coldkey: &str,
 ) -> Result<Response, ContractError> {
+    approve_hotkey_owner(deps.branch(), env.clone(), hotkey, coldkey);
+
     let msg = ExecuteMsg::RootRegister {
         hotkey: hotkey.to_string(),
     };
@@ -246,12 +272,14 @@ pub fn root_register(
 }
 
 pub fn burned_register_ok_neuron(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     netuid: u16,
     hotkey: &str,
     coldkey: &str,
 ) -> Result<Response, ContractError> {
+    approve_hotkey_owner(deps.branch(), env.clone(), hotkey, coldkey);
+
     let msg = ExecuteMsg::BurnedRegister {
         netuid,
         hotkey: hotkey.to_string(),
@@ -771,7 +771,7 @@ fn test_burn_adjustment_case_b() {
     let adjustment_interval = 1;
     let target_registrations_per_interval = 1;
     let start_diff: u64 = 20_000;
-    let mut curr_block_num = 0;
+    let mut curr_block_num = env.block.height;
     add_network(&mut deps.storage, netuid, tempo, 0);
     set_burn(&mut deps.storage, netuid, burn_cost);
     set_min_difficulty(&mut deps.storage, netuid, 1);
@@ -867,7 +867,7 @@ fn test_burn_adjustment_case_c() {
     let adjustment_interval = 1;
     let target_registrations_per_interval = 4; // Needs registrations < 4 to trigger
     let start_diff: u64 = 20_000;
-    let mut curr_block_num = 0;
+    let mut curr_block_num = env.block.height;
     add_network(&mut deps.storage, netuid, tempo, 0);
     set_burn(&mut deps.storage, netuid, burn_cost);
     set_min_difficulty(&mut deps.storage, netuid, 1);
@@ -968,7 +968,7 @@ fn test_burn_adjustment_case_d() {
     let adjustment_interval = 1;
     let target_registrations_per_interval = 4; // Needs registrations < 4 to trigger
     let start_diff: u64 = 20_000;
-    let mut curr_block_num = 0;
+    let mut curr_block_num = env.block.height;
     add_network(&mut deps.storage, netuid, tempo, 0);
     set_burn(&mut deps.storage, netuid, burn_cost);
     set_min_difficulty(&mut deps.storage, netuid, 1);
@@ -1064,7 +1064,7 @@ fn test_burn_adjustment_case_e() {
     let adjustment_interval = 1;
     let target_registrations_per_interval: u16 = 3;
     let start_diff: u64 = 20_000;
-    let mut curr_block_num = 0;
+    let mut curr_block_num = env.block.height;
     add_network(&mut deps.storage, netuid, tempo, 0);
     set_max_registrations_per_block(&mut deps.storage, netuid, 10);
     set_burn(&mut deps.storage, netuid, burn_cost);
@@ -1145,7 +1145,7 @@ fn test_burn_adjustment_case_f() {
     let adjustment_interval = 1;
     let target_registrations_per_interval: u16 = 1;
     let start_diff: u64 = 20_000;
-    let mut curr_block_num = 0;
+    let mut curr_block_num = env.block.height;
     add_network(&mut deps.storage, netuid, tempo, 0);
     set_max_registrations_per_block(&mut deps.storage, netuid, 10);
     set_burn(&mut deps.storage, netuid, burn_cost);
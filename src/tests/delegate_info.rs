@@ -0,0 +1,153 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{Addr, Decimal, Empty, Order};
+use cw2::{get_contract_version, set_contract_version};
+
+use crate::contract::{execute, migrate};
+use crate::delegate_info::{
+    get_delegate_by_existing_account, get_delegated, get_delegates, get_nominators,
+};
+use crate::msg::{ExecuteMsg, MigrateMsg};
+use crate::staking::{create_account_if_non_existent, delegate_hotkey};
+use crate::state::{
+    BLOCKS_PER_DAY, DELEGATES_BY_STAKE, EMISSION, STAKE, STAKED_HOTKEYS, TOTAL_CLAIMABLE,
+};
+use crate::test_helpers::{add_stake, instantiate_contract, root_register};
+use crate::utils::{set_max_registrations_per_block, set_target_registrations_per_interval};
+use crate::ContractError;
+
+#[test]
+fn test_delegate_returns_use_take_and_blocks_per_day() {
+    let (mut deps, env) = instantiate_contract();
+
+    let root_netuid: u16 = 0;
+    let hotkey = Addr::unchecked("addr1");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 1_000_000_000).is_ok());
+    EMISSION
+        .save(&mut deps.storage, root_netuid, &vec![100])
+        .unwrap();
+
+    // Only root can change the block time
+    let msg = ExecuteMsg::SudoSetBlocksPerDay { blocks_per_day: 1000 };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr101", &[]), msg.clone()),
+        Err(ContractError::Unauthorized {})
+    );
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("root", &[]), msg).is_ok());
+
+    // Without take delegators get the whole emission, root tempo is 1.
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    let delegate_info = get_delegate_by_existing_account(&deps.storage, &hotkey);
+    assert_eq!(delegate_info.total_daily_return.amount.u128(), 100_000);
+    assert_eq!(delegate_info.return_per_giga.amount.u128(), 100_000);
+    assert_eq!(delegate_info.subnet_returns.len(), 1);
+    assert_eq!(delegate_info.subnet_returns[0].netuid, root_netuid);
+    assert_eq!(delegate_info.subnet_returns[0].return_per_giga.amount.u128(), 100_000);
+    // 100_000 * 365 / 1_000_000_000 in percents
+    assert_eq!(delegate_info.apr, Decimal::from_ratio(365u128, 100u128));
+
+    // With the full take delegators get nothing.
+    delegate_hotkey(&mut deps.storage, &hotkey, u16::MAX);
+    let delegate_info = get_delegate_by_existing_account(&deps.storage, &hotkey);
+    assert_eq!(delegate_info.total_daily_return.amount.u128(), 100_000);
+    assert_eq!(delegate_info.return_per_giga.amount.u128(), 0);
+    assert_eq!(delegate_info.apr, Decimal::zero());
+}
+
+#[test]
+fn test_delegate_queries_cap_limit() {
+    let (mut deps, _env) = instantiate_contract();
+
+    for i in 0..300 {
+        let hotkey = Addr::unchecked(format!("hot{:03}", i));
+        let coldkey = Addr::unchecked(format!("cold{:03}", i));
+        create_account_if_non_existent(&mut deps.storage, &coldkey, &hotkey);
+        delegate_hotkey(&mut deps.storage, &hotkey, 0);
+        STAKE
+            .save(&mut deps.storage, (&hotkey, &coldkey), &1)
+            .unwrap();
+        STAKE
+            .save(
+                &mut deps.storage,
+                (&Addr::unchecked("hot000"), &coldkey),
+                &1,
+            )
+            .unwrap();
+        STAKED_HOTKEYS
+            .save(
+                &mut deps.storage,
+                (&Addr::unchecked("cold000"), &hotkey),
+                &Empty {},
+            )
+            .unwrap();
+    }
+
+    let delegates = get_delegates(deps.as_ref(), None, Some(u16::MAX), None).unwrap();
+    assert_eq!(delegates.len(), 64);
+    let delegated =
+        get_delegated(deps.as_ref(), "cold000".to_string(), None, Some(u16::MAX)).unwrap();
+    assert_eq!(delegated.len(), 64);
+    let nominators =
+        get_nominators(deps.as_ref(), "hot000".to_string(), None, Some(u16::MAX)).unwrap();
+    assert_eq!(nominators.len(), 256);
+}
+
+#[test]
+fn test_migrate_from_0_3_0_builds_indexes() {
+    let (mut deps, env) = instantiate_contract();
+
+    set_max_registrations_per_block(&mut deps.storage, 0, 2);
+    set_target_registrations_per_interval(&mut deps.storage, 0, 2);
+    for (hot, cold) in [("addr1", "addr101"), ("addr2", "addr102")] {
+        assert!(root_register(deps.as_mut(), env.clone(), hot, cold).is_ok());
+    }
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr2", "addr200", 300).is_ok());
+
+    // State as left by the 0.3.0 contract, without the indexes and new items
+    DELEGATES_BY_STAKE.clear(&mut deps.storage);
+    STAKED_HOTKEYS.clear(&mut deps.storage);
+    BLOCKS_PER_DAY.remove(&mut deps.storage);
+    TOTAL_CLAIMABLE.remove(&mut deps.storage);
+    set_contract_version(&mut deps.storage, "cybernet", "0.3.0").unwrap();
+    assert!(get_delegates(deps.as_ref(), None, None, Some(true))
+        .unwrap()
+        .is_empty());
+
+    assert!(migrate(deps.as_mut(), env.clone(), MigrateMsg {}).is_ok());
+    assert_eq!(
+        get_contract_version(&deps.storage).unwrap().version,
+        env!("CARGO_PKG_VERSION")
+    );
+    assert_eq!(BLOCKS_PER_DAY.load(&deps.storage).unwrap(), 14400);
+    assert_eq!(TOTAL_CLAIMABLE.load(&deps.storage).unwrap(), 0);
+    assert_eq!(
+        get_delegates(deps.as_ref(), None, None, Some(true))
+            .unwrap()
+            .iter()
+            .map(|d| d.delegate.to_string())
+            .collect::<Vec<String>>(),
+        vec!["addr2", "addr1"]
+    );
+    assert_eq!(
+        get_delegated(deps.as_ref(), "addr200".to_string(), None, None)
+            .unwrap()
+            .iter()
+            .map(|(d, s)| (d.delegate.to_string(), *s))
+            .collect::<Vec<(String, u64)>>(),
+        vec![("addr1".to_string(), 100), ("addr2".to_string(), 300)]
+    );
+    assert_eq!(
+        STAKED_HOTKEYS
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .count(),
+        2
+    );
+
+    // Already migrated
+    assert_eq!(
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}),
+        Err(ContractError::MigrationError {})
+    );
+}
@@ -1118,7 +1118,7 @@ fn test_bonds() {
     let n: u16 = 8;
     let netuid: u16 = 2;
     let tempo: u16 = u16::MAX - 1; // high tempo to skip automatic epochs in on_initialize, use manual epochs instead
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let max_stake: u64 = 4;
     let stakes: Vec<u64> = vec![1, 2, 3, 4, 0, 0, 0, 0];
     add_network(&mut deps.storage, netuid, tempo, 0);
@@ -1573,7 +1573,7 @@ fn test_active_stake() {
     let n: u16 = 4;
     let netuid: u16 = 2;
     let tempo: u16 = u16::MAX - 1; // high tempo to skip automatic epochs in on_initialize, use manual epochs instead
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let stake: u64 = 1;
     add_network(&mut deps.storage, netuid, tempo, 0);
     set_max_allowed_uids(&mut deps.storage, netuid, n);
@@ -1,4 +1,5 @@
 mod block_step;
+mod delegate_info;
 mod difficulty;
 mod epoch;
 mod graph;
@@ -6,5 +7,7 @@ mod neuron_info;
 mod registration;
 mod root;
 mod serving;
+mod slashing;
+mod staking;
 mod uids;
 mod weights;
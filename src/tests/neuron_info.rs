@@ -1,7 +1,24 @@
+use cosmwasm_std::testing::mock_info;
 use cosmwasm_std::Addr;
 
-use crate::neuron_info::{get_neuron, get_neurons};
-use crate::test_helpers::{add_network, instantiate_contract, register_ok_neuron};
+use crate::contract::execute;
+use crate::msg::ExecuteMsg;
+use crate::neuron_info::{
+    get_neuron, get_neuron_lite, get_neurons, get_neurons_lite, get_pruning_candidates,
+};
+use crate::registration::get_neuron_to_prune;
+use crate::root::set_emission_values;
+use crate::staking::{delegate_hotkey, get_stake_for_coldkey_and_hotkey};
+use crate::state::{BLOCK_AT_REGISTRATION, STAKE};
+use crate::test_helpers::{
+    add_network, add_stake, burned_register_ok_neuron, instantiate_contract, register_ok_neuron,
+    run_step_to_block,
+};
+use crate::uids::remove_neuron;
+use crate::utils::{
+    set_burn, set_immunity_period, set_max_registrations_per_block, set_pruning_score_for_uid,
+    set_target_registrations_per_interval,
+};
 
 #[test]
 fn test_get_neuron_none() {
@@ -73,6 +90,66 @@ fn test_get_neurons_list() {
     assert_eq!(neurons.len(), neuron_count as usize);
 }
 
+#[test]
+fn test_get_neurons_skip_vacant_uid() {
+    let (mut deps, env) = instantiate_contract();
+    let netuid: u16 = 1;
+
+    add_network(&mut deps.storage, netuid, 2, 0);
+    set_max_registrations_per_block(&mut deps.storage, netuid, 3);
+    set_target_registrations_per_interval(&mut deps.storage, netuid, 3);
+    for (index, key) in ["addr1", "addr2", "addr3"].iter().enumerate() {
+        register_ok_neuron(deps.as_mut(), env.clone(), netuid, key, key, index as u64).unwrap();
+    }
+
+    let msg = ExecuteMsg::Deregister {
+        netuid,
+        hotkey: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("addr2", &[]), msg).unwrap();
+
+    // Neurons after the vacant uid are still listed
+    let neurons = get_neurons(&deps.storage, netuid).unwrap();
+    let expected = [0, 2].map(|uid| get_neuron(&deps.storage, netuid, uid).unwrap().unwrap());
+    assert_eq!(neurons, expected);
+    let neurons = get_neurons_lite(&deps.storage, netuid).unwrap();
+    let expected = [0, 2].map(|uid| {
+        get_neuron_lite(&deps.storage, netuid, uid)
+            .unwrap()
+            .unwrap()
+    });
+    assert_eq!(neurons, expected);
+}
+
+#[test]
+fn test_get_neuron_stake_includes_pending_rewards() {
+    let (mut deps, mut env) = instantiate_contract();
+    let netuid: u16 = 1;
+    let tempo: u16 = 2;
+
+    let hotkey = Addr::unchecked("addr1");
+    let nominator = Addr::unchecked("addr200");
+
+    add_network(&mut deps.storage, netuid, tempo, 0);
+    set_burn(&mut deps.storage, netuid, 1);
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr1", "addr101").unwrap();
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 1000).is_ok());
+    set_emission_values(&mut deps.storage, &deps.api, &vec![netuid], vec![1000]).unwrap();
+
+    // Run the subnet epoch and drain its emission
+    run_step_to_block(deps.as_mut(), &mut env, 2 * (tempo as u64 + 1)).unwrap();
+    let stake = get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator, &hotkey);
+    assert!(stake > STAKE.load(&deps.storage, (&hotkey, &nominator)).unwrap());
+
+    // The owner entry is created at registration
+    let expected = vec![(Addr::unchecked("addr101"), 0), (nominator, stake)];
+    let neuron = get_neuron(&deps.storage, netuid, 0).unwrap().unwrap();
+    assert_eq!(neuron.stake, expected);
+    let neuron = get_neuron_lite(&deps.storage, netuid, 0).unwrap().unwrap();
+    assert_eq!(neuron.stake, expected);
+}
+
 #[test]
 fn test_get_neurons_empty() {
     let (deps, _) = instantiate_contract();
@@ -82,3 +159,103 @@ fn test_get_neurons_empty() {
     let neurons = get_neurons(&deps.storage, netuid).unwrap();
     assert_eq!(neurons.len(), neuron_count as usize);
 }
+
+#[test]
+fn test_get_pruning_candidates() {
+    let (mut deps, env) = instantiate_contract();
+    let netuid: u16 = 1;
+
+    add_network(&mut deps.storage, netuid, 2, 2);
+    set_max_registrations_per_block(&mut deps.storage, netuid, 4);
+    set_target_registrations_per_interval(&mut deps.storage, netuid, 4);
+    for (index, hotkey) in ["addr0", "addr1", "addr2", "addr3"].iter().enumerate() {
+        register_ok_neuron(
+            deps.as_mut(),
+            env.clone(),
+            netuid,
+            hotkey,
+            hotkey,
+            39420842 + index as u64 * 1000,
+        )
+        .unwrap();
+    }
+    set_immunity_period(&mut deps.storage, netuid, 5);
+    set_pruning_score_for_uid(&mut deps.storage, &deps.api, netuid, 0, 50);
+    set_pruning_score_for_uid(&mut deps.storage, &deps.api, netuid, 1, 20);
+    set_pruning_score_for_uid(&mut deps.storage, &deps.api, netuid, 2, 20);
+    set_pruning_score_for_uid(&mut deps.storage, &deps.api, netuid, 3, 10);
+    // uid 3 registered recently and is still immune
+    let current_block = 10;
+    BLOCK_AT_REGISTRATION
+        .save(&mut deps.storage, (netuid, 3), &8)
+        .unwrap();
+
+    let candidates = get_pruning_candidates(&deps.storage, netuid, current_block, None).unwrap();
+    let uids: Vec<u16> = candidates.iter().map(|c| c.uid).collect();
+    assert_eq!(uids, vec![2, 1, 0, 3]);
+    assert_eq!(candidates[0].hotkey, Some(Addr::unchecked("addr2")));
+    assert_eq!(candidates[0].pruning_score, 20);
+    assert_eq!(candidates[0].immunity_remaining, 0);
+    assert_eq!(candidates[3].block_at_registration, 8);
+    assert_eq!(candidates[3].immunity_remaining, 3);
+    assert_eq!(
+        get_neuron_to_prune(&mut deps.storage, &deps.api, netuid, current_block),
+        2
+    );
+
+    // Vacant slots go first, limit caps the result
+    remove_neuron(&mut deps.storage, netuid, 1).unwrap();
+    let candidates = get_pruning_candidates(&deps.storage, netuid, current_block, Some(2)).unwrap();
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0].uid, 1);
+    assert_eq!(candidates[0].hotkey, None);
+    assert_eq!(
+        get_neuron_to_prune(&mut deps.storage, &deps.api, netuid, current_block),
+        1
+    );
+}
+
+#[test]
+fn test_get_pruning_candidates_first_is_neuron_to_prune() {
+    let (mut deps, env) = instantiate_contract();
+    let netuid: u16 = 1;
+
+    add_network(&mut deps.storage, netuid, 2, 2);
+    set_max_registrations_per_block(&mut deps.storage, netuid, 3);
+    set_target_registrations_per_interval(&mut deps.storage, netuid, 3);
+    for (index, hotkey) in ["addr0", "addr1", "addr2"].iter().enumerate() {
+        register_ok_neuron(
+            deps.as_mut(),
+            env.clone(),
+            netuid,
+            hotkey,
+            hotkey,
+            index as u64,
+        )
+        .unwrap();
+    }
+    for uid in 0..3 {
+        set_pruning_score_for_uid(&mut deps.storage, &deps.api, netuid, uid, u16::MAX);
+    }
+    let current_block = env.block.height + 10;
+
+    // No neuron is immune and all have the max pruning score
+    set_immunity_period(&mut deps.storage, netuid, 0);
+    let candidates = get_pruning_candidates(&deps.storage, netuid, current_block, None).unwrap();
+    assert_eq!(candidates.len(), 3);
+    assert_eq!(candidates[0].uid, 0);
+    assert_eq!(
+        candidates[0].uid,
+        get_neuron_to_prune(&mut deps.storage, &deps.api, netuid, current_block)
+    );
+
+    // All neurons are immune
+    set_immunity_period(&mut deps.storage, netuid, 100);
+    set_pruning_score_for_uid(&mut deps.storage, &deps.api, netuid, 2, 10);
+    let candidates = get_pruning_candidates(&deps.storage, netuid, current_block, None).unwrap();
+    assert_eq!(candidates[0].uid, 2);
+    assert_eq!(
+        candidates[0].uid,
+        get_neuron_to_prune(&mut deps.storage, &deps.api, netuid, current_block)
+    );
+}
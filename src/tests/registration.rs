@@ -1,12 +1,21 @@
 use cosmwasm_std::testing::mock_info;
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{coin, coins, from_json, Addr, BankMsg, CosmosMsg, Order, Uint128};
 
-use crate::contract::execute;
-use crate::msg::ExecuteMsg;
-use crate::registration::{create_work_for_block_number, get_neuron_to_prune};
+use crate::contract::{execute, query};
+use crate::msg::{ExecuteMsg, QueryMsg};
+use crate::registration::{
+    create_seal_hash, create_work_for_block_number, get_block_hash_from_u64, get_neuron_to_prune,
+    hash_to_vec, prune_used_work,
+};
 use crate::serving::get_axon_info;
-use crate::staking::get_owning_coldkey_for_hotkey;
-use crate::state::AxonInfoOf;
+use crate::staking::{
+    get_claimable_for_coldkey, get_locked_stake, get_owning_coldkey_for_hotkey,
+    get_stake_for_coldkey_and_hotkey,
+};
+use crate::state::{
+    AxonInfoOf, FaucetConfig, RegistrationFeeDestination, RegistrationMode, FAUCET_LAST_PAYOUT,
+    OWNER, SUBNET_OWNER, USED_WORK,
+};
 use crate::test_helpers::{
     add_balance_to_coldkey_account, add_network, burned_register_ok_neuron, instantiate_contract,
     pow_register_ok_neuron, register_ok_neuron, run_step_to_block, step_block,
@@ -16,12 +25,13 @@ use crate::uids::{
     get_uid_for_net_and_hotkey, is_uid_exist_on_network,
 };
 use crate::utils::{
-    get_burn_as_u64, get_difficulty_as_u64, get_emission_value, get_immunity_period,
-    get_max_allowed_uids, get_max_registrations_per_block, get_neuron_block_at_registration,
-    get_pruning_score_for_uid, get_rao_recycled, get_registrations_this_block,
-    get_registrations_this_interval, get_target_registrations_per_interval, get_tempo,
-    set_adjustment_interval, set_burn, set_difficulty, set_immunity_period, set_max_allowed_uids,
-    set_max_registrations_per_block, set_network_registration_allowed, set_pruning_score_for_uid,
+    get_burn_as_u64, get_difficulty_as_u64, get_emission_value, get_faucet_reserve,
+    get_immunity_period, get_max_allowed_uids, get_max_registrations_per_block,
+    get_neuron_block_at_registration, get_pruning_score_for_uid, get_rao_recycled,
+    get_registrations_this_block, get_registrations_this_interval,
+    get_target_registrations_per_interval, get_tempo, set_adjustment_interval, set_burn,
+    set_difficulty, set_immunity_period, set_max_allowed_uids, set_max_registrations_per_block,
+    set_network_registration_allowed, set_pruning_score_for_uid,
     set_target_registrations_per_interval,
 };
 use crate::ContractError;
@@ -94,7 +104,7 @@ fn test_registration_difficulty() {
 fn test_registration_ok() {
     let (mut deps, env) = instantiate_contract();
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let netuid: u16 = 2;
     let tempo: u16 = 13;
     let hotkey_account_id = "addr1";
@@ -216,6 +226,702 @@ fn test_burned_registration_ok() {
     )
 }
 
+#[test]
+fn test_burned_registration_requires_hotkey_approval() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    let hotkey = "addr1";
+    let coldkey = "addr667";
+    let squatter = "addr666";
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_burn(&mut deps.storage, netuid, 1);
+
+    let register = |coldkey: &str| {
+        let msg = ExecuteMsg::BurnedRegister {
+            netuid,
+            hotkey: hotkey.to_string(),
+        };
+        (mock_info(coldkey, &[coin(1, "boot")]), msg)
+    };
+
+    // The hotkey approved nobody yet
+    let (info, msg) = register(squatter);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::HotkeyOwnershipNotApproved {});
+
+    // The hotkey approves its own coldkey, others still can't claim it
+    let msg = ExecuteMsg::ApproveHotkeyOwner {
+        coldkey: coldkey.to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(hotkey, &[]), msg).unwrap();
+
+    let (info, msg) = register(squatter);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::HotkeyOwnershipNotApproved {});
+
+    let (info, msg) = register(coldkey);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        get_owning_coldkey_for_hotkey(&deps.storage, &Addr::unchecked(hotkey)),
+        coldkey
+    );
+
+    // The approval is consumed and the owned hotkey can't approve again
+    let msg = ExecuteMsg::ApproveHotkeyOwner {
+        coldkey: squatter.to_string(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(hotkey, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyRegistered {});
+}
+
+#[test]
+fn test_deregister_frees_slot_for_next_registrant() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_burn(&mut deps.storage, netuid, 1);
+    set_max_allowed_uids(&mut deps.storage, netuid, 2);
+
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr1", "addr11").unwrap();
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr2", "addr22").unwrap();
+
+    // Only the hotkey or its owner can leave the subnet
+    let msg = ExecuteMsg::Deregister {
+        netuid,
+        hotkey: Some("addr1".to_string()),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr22", &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::NonAssociatedColdKey {});
+    execute(deps.as_mut(), env.clone(), mock_info("addr11", &[]), msg).unwrap();
+
+    // The slot is vacant but the subnet size is unchanged
+    assert_eq!(get_subnetwork_n(&deps.storage, netuid), 2);
+    assert_eq!(is_uid_exist_on_network(&deps.storage, netuid, 0), false);
+    assert!(get_uid_for_net_and_hotkey(&deps.storage, netuid, &Addr::unchecked("addr1")).is_err());
+
+    // The hotkey already left the subnet
+    let msg = ExecuteMsg::Deregister { netuid, hotkey: None };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr1", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::NotRegistered {});
+
+    // The next registrant takes the vacant slot instead of pruning the active neuron
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr3", "addr33").unwrap();
+    assert_eq!(
+        get_uid_for_net_and_hotkey(&deps.storage, netuid, &Addr::unchecked("addr3")).unwrap(),
+        0
+    );
+    assert_eq!(
+        get_uid_for_net_and_hotkey(&deps.storage, netuid, &Addr::unchecked("addr2")).unwrap(),
+        1
+    );
+}
+
+#[test]
+fn test_burned_registration_refunds_overpayment() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_burn(&mut deps.storage, netuid, 1000);
+
+    let msg = ExecuteMsg::BurnedRegister {
+        netuid,
+        hotkey: "addr1".to_string(),
+    };
+    let info = mock_info("addr1", &[coin(1500, "boot")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr1".to_string(),
+            amount: coins(500, "boot"),
+        })
+    );
+    assert!(res.attributes.iter().any(|a| a.key == "refunded" && a.value == "500"));
+}
+
+#[test]
+fn test_burned_registration_fee_destination() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_burn(&mut deps.storage, netuid, 1000);
+    SUBNET_OWNER.save(&mut deps.storage, netuid, &Addr::unchecked("owner")).unwrap();
+
+    // Fees are burned by default
+    let res = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr1", "addr1").unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: coins(1000, "boot"),
+        })
+    );
+
+    // Only root routes the fees
+    let msg = ExecuteMsg::SudoSetRegistrationFeeDestination {
+        netuid,
+        destination: RegistrationFeeDestination::SubnetOwner,
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info("root", &[]), msg).unwrap();
+
+    let res = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr2", "addr2").unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "owner".to_string(),
+            amount: coins(1000, "boot"),
+        })
+    );
+
+    // Recycled fees stay in the contract
+    let msg = ExecuteMsg::SudoSetRegistrationFeeDestination {
+        netuid,
+        destination: RegistrationFeeDestination::Recycle,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info("root", &[]), msg).unwrap();
+
+    let res = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr3", "addr3").unwrap();
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn test_registration_mode() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    let owner = "owner";
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_burn(&mut deps.storage, netuid, 1);
+    SUBNET_OWNER
+        .save(&mut deps.storage, netuid, &Addr::unchecked(owner))
+        .unwrap();
+
+    let set_mode = |mode: RegistrationMode| ExecuteMsg::SudoSetRegistrationMode { netuid, mode };
+
+    // Burn only subnet rejects PoW
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        set_mode(RegistrationMode::Burn),
+    )
+    .unwrap();
+    let err =
+        register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr1", "addr1", 0).unwrap_err();
+    assert_eq!(err, ContractError::RegistrationModeDisabled {});
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr1", "addr1").unwrap();
+
+    // PoW only subnet rejects burned registrations
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        set_mode(RegistrationMode::Pow),
+    )
+    .unwrap();
+    let err = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr2", "addr2")
+        .unwrap_err();
+    assert_eq!(err, ContractError::RegistrationModeDisabled {});
+    register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr2", "addr2", 0).unwrap();
+
+    // Owner registers on permissioned subnets only
+    let sudo_register = |hotkey: &str| ExecuteMsg::SudoRegister {
+        netuid,
+        hotkey: hotkey.to_string(),
+        coldkey: hotkey.to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[coin(1, "boot")]),
+        sudo_register("addr3"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RegistrationModeDisabled {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        set_mode(RegistrationMode::Permissioned),
+    )
+    .unwrap();
+    let err = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr3", "addr3")
+        .unwrap_err();
+    assert_eq!(err, ContractError::RegistrationModeDisabled {});
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr3", &[coin(1, "boot")]),
+        sudo_register("addr3"),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[coin(1, "boot")]),
+        sudo_register("addr3"),
+    )
+    .unwrap();
+    assert_eq!(get_subnetwork_n(&deps.storage, netuid), 3);
+}
+
+#[test]
+fn test_owner_sudo_registration_requires_hotkey_approval() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    let owner = "owner";
+    let hotkey = "addr1";
+    let coldkey = "addr667";
+    add_network(&mut deps.storage, netuid, 13, 0);
+    SUBNET_OWNER
+        .save(&mut deps.storage, netuid, &Addr::unchecked(owner))
+        .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::SudoSetRegistrationMode {
+            netuid,
+            mode: RegistrationMode::Permissioned,
+        },
+    )
+    .unwrap();
+
+    let sudo_register = |coldkey: &str| ExecuteMsg::SudoRegister {
+        netuid,
+        hotkey: hotkey.to_string(),
+        coldkey: coldkey.to_string(),
+    };
+
+    // The owner of a permissioned subnet can't claim an unowned hotkey for itself
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[coin(1, "boot")]),
+        sudo_register(owner),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::HotkeyOwnershipNotApproved {});
+    assert!(!OWNER.has(&deps.storage, &Addr::unchecked(hotkey)));
+    assert_eq!(get_subnetwork_n(&deps.storage, netuid), 0);
+
+    // Once the hotkey approves a coldkey the owner may register it for that coldkey only
+    let msg = ExecuteMsg::ApproveHotkeyOwner {
+        coldkey: coldkey.to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(hotkey, &[]), msg).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[coin(1, "boot")]),
+        sudo_register(owner),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::HotkeyOwnershipNotApproved {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[coin(1, "boot")]),
+        sudo_register(coldkey),
+    )
+    .unwrap();
+    assert_eq!(
+        get_owning_coldkey_for_hotkey(&deps.storage, &Addr::unchecked(hotkey)),
+        coldkey
+    );
+    assert_eq!(get_subnetwork_n(&deps.storage, netuid), 1);
+}
+
+#[test]
+fn test_registration_allowlist() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    let owner = "owner";
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_burn(&mut deps.storage, netuid, 1);
+    set_max_registrations_per_block(&mut deps.storage, netuid, 6);
+    set_target_registrations_per_interval(&mut deps.storage, netuid, 6);
+    SUBNET_OWNER
+        .save(&mut deps.storage, netuid, &Addr::unchecked(owner))
+        .unwrap();
+
+    // Only the subnet owner or root manages the allowlist
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr1", &[]),
+        ExecuteMsg::SudoSetRegistrationAllowlistEnabled {
+            netuid,
+            enabled: true,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::SudoSetRegistrationAllowlistEnabled {
+            netuid,
+            enabled: true,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::SudoAddToRegistrationAllowlist {
+            netuid,
+            addresses: vec![
+                "hotkey1".to_string(),
+                "coldkey2".to_string(),
+                "hotkey5".to_string(),
+            ],
+        },
+    )
+    .unwrap();
+
+    // Listed hotkey or listed signing coldkey registers, others are rejected
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey1", "coldkey1").unwrap();
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey2", "coldkey2").unwrap();
+    let err = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey3", "coldkey3")
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotInRegistrationAllowlist {});
+    let err = register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey3", "coldkey3", 1)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotInRegistrationAllowlist {});
+
+    // PoW work is signed by the hotkey only, naming a listed coldkey doesn't pass
+    let err = register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey3", "coldkey2", 2)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotInRegistrationAllowlist {});
+    register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey5", "coldkey5", 3).unwrap();
+
+    let allowlist: Vec<String> = from_json(
+        query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::GetRegistrationAllowlist {
+                netuid,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        allowlist,
+        vec![
+            "coldkey2".to_string(),
+            "hotkey1".to_string(),
+            "hotkey5".to_string()
+        ]
+    );
+
+    // Removed keys are rejected again, disabled allowlist lets everyone in
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::SudoRemoveFromRegistrationAllowlist {
+            netuid,
+            addresses: vec!["hotkey1".to_string()],
+        },
+    )
+    .unwrap();
+    let err = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey1", "coldkey4")
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotInRegistrationAllowlist {});
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        ExecuteMsg::SudoSetRegistrationAllowlistEnabled {
+            netuid,
+            enabled: false,
+        },
+    )
+    .unwrap();
+    burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "hotkey3", "coldkey3").unwrap();
+    assert_eq!(get_subnetwork_n(&deps.storage, netuid), 4);
+}
+
+#[test]
+fn test_used_work_pruned_after_work_window() {
+    let (mut deps, mut env) = instantiate_contract();
+
+    // Expired work of old blocks and fresh work of the current block
+    let current_block = env.block.height + 10;
+    for i in 0..100u8 {
+        USED_WORK
+            .save(&mut deps.storage, vec![0, i], &(current_block - 5))
+            .unwrap();
+    }
+    for i in 0..3u8 {
+        USED_WORK
+            .save(&mut deps.storage, vec![1, i], &current_block)
+            .unwrap();
+    }
+
+    // Pruning is bounded per call and continues from the previous position
+    assert_eq!(prune_used_work(&mut deps.storage, current_block).unwrap(), 64);
+    assert_eq!(prune_used_work(&mut deps.storage, current_block).unwrap(), 36);
+    assert_eq!(prune_used_work(&mut deps.storage, current_block).unwrap(), 0);
+    let remaining = USED_WORK
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .count();
+    assert_eq!(remaining, 3);
+
+    // Block step removes the fresh work once it leaves the window
+    run_step_to_block(deps.as_mut(), &mut env, current_block + 3).unwrap();
+    assert!(USED_WORK.is_empty(&deps.storage));
+}
+
+#[test]
+fn test_staked_registration() {
+    let (mut deps, mut env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    let owner = "owner";
+    let hotkey = "addr1";
+    let coldkey = "addr667";
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_immunity_period(&mut deps.storage, netuid, 10);
+    SUBNET_OWNER
+        .save(&mut deps.storage, netuid, &Addr::unchecked(owner))
+        .unwrap();
+
+    let register = |amount: u128| {
+        let msg = ExecuteMsg::StakedRegister {
+            netuid,
+            hotkey: hotkey.to_string(),
+        };
+        (mock_info(coldkey, &[coin(amount, "boot")]), msg)
+    };
+
+    // Stake registrations need the stake registration mode
+    let (info, msg) = register(100);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::RegistrationModeDisabled {});
+
+    let set_mode = ExecuteMsg::SudoSetRegistrationMode {
+        netuid,
+        mode: RegistrationMode::Stake,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), set_mode).unwrap();
+    let set_stake = ExecuteMsg::SudoSetRegistrationStake {
+        netuid,
+        registration_stake: 100,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), set_stake).unwrap();
+    let approve = ExecuteMsg::ApproveHotkeyOwner {
+        coldkey: coldkey.to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(hotkey, &[]), approve).unwrap();
+    let err = burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, "addr2", "addr2")
+        .unwrap_err();
+    assert_eq!(err, ContractError::RegistrationModeDisabled {});
+
+    // The paid tokens must cover the registration stake
+    let (info, msg) = register(99);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::NotEnoughTokens {});
+
+    // The paid tokens are staked on the hotkey and locked for the immunity period
+    let (info, msg) = register(150);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert!(res.messages.is_empty());
+    let hotkey_addr = Addr::unchecked(hotkey);
+    let coldkey_addr = Addr::unchecked(coldkey);
+    assert_eq!(
+        get_uid_for_net_and_hotkey(&deps.storage, netuid, &hotkey_addr),
+        Ok(0)
+    );
+    assert_eq!(
+        get_stake_for_coldkey_and_hotkey(&deps.storage, &coldkey_addr, &hotkey_addr),
+        150
+    );
+    let unlock_block = env.block.height + 10;
+    assert_eq!(
+        get_locked_stake(&deps.storage, &coldkey_addr, &hotkey_addr, env.block.height),
+        150
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(coldkey, &[]),
+        ExecuteMsg::RemoveStake {
+            hotkey: hotkey.to_string(),
+            amount: 1,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StakeLocked {});
+    let deregister = ExecuteMsg::Deregister {
+        netuid,
+        hotkey: Some(hotkey.to_string()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(coldkey, &[]),
+        deregister.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StakeLocked {});
+
+    // After the immunity period the registrant leaves and gets the stake back
+    env.block.height = unlock_block;
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(coldkey, &[]),
+        deregister,
+    )
+    .unwrap();
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &coldkey_addr), 150);
+}
+
+#[test]
+fn test_faucet() {
+    let (mut deps, env) = instantiate_contract();
+
+    let faucet = |coldkey: &str, nonce: u64, deps: &cosmwasm_std::OwnedDeps<_, _, _>| {
+        let seal = create_seal_hash(&deps.storage, env.block.height, nonce, coldkey).unwrap();
+        let msg = ExecuteMsg::Faucet {
+            block_number: env.block.height,
+            nonce,
+            work: hash_to_vec(seal),
+        };
+        (mock_info(coldkey, &[]), msg)
+    };
+
+    // Root funds the reserve and configures the faucet
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("root", &coins(250, "boot")),
+        ExecuteMsg::SudoFundFaucet {},
+    )
+    .unwrap();
+    let config = FaucetConfig {
+        amount: 100,
+        cooldown: 5,
+        difficulty: 1,
+    };
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("root", &[]),
+        ExecuteMsg::SudoSetFaucetConfig { config },
+    )
+    .unwrap();
+
+    let (info, msg) = faucet("addr1", 0, &deps);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::FaucetDisabled {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("root", &[]),
+        ExecuteMsg::SudoSetAllowFaucet { allow: true },
+    )
+    .unwrap();
+
+    // The work must be the seal of the caller coldkey
+    let (_, msg) = faucet("addr1", 0, &deps);
+    let err = execute(deps.as_mut(), env.clone(), mock_info("addr2", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSeal {});
+
+    // Payout is sent from the reserve
+    let (info, msg) = faucet("addr1", 0, &deps);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr1".to_string(),
+            amount: coins(100, "boot"),
+        })
+    );
+    assert_eq!(get_faucet_reserve(&deps.storage), 150);
+
+    // Used work and payouts within the cooldown are rejected
+    let (info, msg) = faucet("addr1", 0, &deps);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::FaucetCooldown {});
+    FAUCET_LAST_PAYOUT.remove(&mut deps.storage, &Addr::unchecked("addr1"));
+    let (info, msg) = faucet("addr1", 0, &deps);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::WorkRepeated {});
+
+    // The reserve bounds the payouts
+    let (info, msg) = faucet("addr2", 0, &deps);
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let (info, msg) = faucet("addr3", 0, &deps);
+    let err = execute(deps.as_mut(), env.clone(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::FaucetReserveDepleted {});
+    assert_eq!(get_faucet_reserve(&deps.storage), 50);
+}
+
+#[test]
+fn test_faucet_reserve_not_swept_on_deactivate() {
+    let (mut deps, mut env) = instantiate_contract();
+    env.contract.address =
+        Addr::unchecked("bostrom1suhgf5svhu4usrurvxzlgn54ksxmn8gljarjtxqnapv8kjnp4nrsdnqnt0");
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("root", &coins(250, "boot")),
+        ExecuteMsg::SudoFundFaucet {},
+    )
+    .unwrap();
+
+    // Only the tokens above the faucet reserve are returned to root
+    deps.querier
+        .update_balance(env.contract.address.clone(), coins(300, "boot"));
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("root", &[]),
+        ExecuteMsg::Deactivate {},
+    )
+    .unwrap();
+    assert_eq!(get_faucet_reserve(&deps.storage), 250);
+    assert_eq!(
+        get_claimable_for_coldkey(&deps.storage, &Addr::unchecked("root")),
+        50
+    );
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), coins(249, "boot"));
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("root", &[]),
+        ExecuteMsg::Deactivate {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ContractBalanceBelowReserved {});
+}
+
 #[test]
 fn test_burn_adjustment() {
     let (mut deps, mut env) = instantiate_contract();
@@ -287,7 +993,7 @@ fn test_registration_too_many_registrations_per_block() {
 
     assert_eq!(get_max_registrations_per_block(&deps.storage, netuid), 10);
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let (nonce0, work0): (u64, Vec<u8>) =
         create_work_for_block_number(&deps.storage, netuid, block_number, 3942084, "addr0");
     let (nonce1, work1): (u64, Vec<u8>) =
@@ -475,7 +1181,7 @@ fn test_registration_too_many_registrations_per_interval() {
     );
     // Then the max is 3 * 3 = 9
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let (nonce0, work0): (u64, Vec<u8>) =
         create_work_for_block_number(&deps.storage, netuid, block_number, 3942084, "addr0");
     let (nonce1, work1): (u64, Vec<u8>) =
@@ -641,7 +1347,7 @@ fn test_registration_immunity_period() { //impl this test when epoch impl and ca
 fn test_registration_already_active_hotkey() {
     let (mut deps, env) = instantiate_contract();
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let netuid: u16 = 2;
     let tempo: u16 = 13;
     let hotkey_account_id = "addr1";
@@ -665,7 +1371,7 @@ fn test_registration_already_active_hotkey() {
     );
     assert!(result.is_ok());
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let hotkey_account_id = "addr1";
     let coldkey_account_id = "addr667";
     let (nonce, work): (u64, Vec<u8>) =
@@ -697,10 +1403,9 @@ fn test_registration_invalid_seal() {
     add_network(&mut deps.storage, netuid, tempo, 0);
 
     let (nonce, work): (u64, Vec<u8>) =
-            // invalid seal because should be because of different block, but we use same block number
             // invalid seal created using addr2 instead of addr1
             create_work_for_block_number(
-                &deps.storage,netuid, 42, 0, "addr2");
+                &deps.storage,netuid, block_number, 0, "addr2");
 
     let result = pow_register_ok_neuron(
         deps.as_mut(),
@@ -729,7 +1434,7 @@ fn test_registration_invalid_block_number() {
     add_network(&mut deps.storage, netuid, tempo, 0);
 
     let (nonce, work): (u64, Vec<u8>) =
-        create_work_for_block_number(&deps.storage, netuid, block_number, 0, &hotkey_account_id);
+        create_work_for_block_number(&deps.storage, netuid, env.block.height, 0, &hotkey_account_id);
 
     let result = pow_register_ok_neuron(
         deps.as_mut(),
@@ -744,11 +1449,54 @@ fn test_registration_invalid_block_number() {
     assert_eq!(result.unwrap_err(), ContractError::InvalidWorkBlock {})
 }
 
+#[test]
+fn test_registration_work_bound_to_block_seed() {
+    let (mut deps, mut env) = instantiate_contract();
+
+    let netuid: u16 = 2;
+    let hotkey_account_id = "addr1";
+    let coldkey_account_id = "addr667";
+    add_network(&mut deps.storage, netuid, 13, 0);
+
+    // Work done for the current block can't be replayed for the next one
+    let block_number = env.block.height;
+    let (nonce, work): (u64, Vec<u8>) =
+        create_work_for_block_number(&deps.storage, netuid, block_number, 0, hotkey_account_id);
+    step_block(deps.as_mut(), &mut env).unwrap();
+
+    let result = pow_register_ok_neuron(
+        deps.as_mut(),
+        env.clone(),
+        netuid,
+        env.block.height,
+        nonce,
+        work.clone(),
+        hotkey_account_id,
+        coldkey_account_id,
+    );
+    assert_eq!(result.unwrap_err(), ContractError::InvalidSeal {});
+
+    // Seeds of blocks out of the work window are dropped
+    run_step_to_block(deps.as_mut(), &mut env, block_number + 3).unwrap();
+    assert!(get_block_hash_from_u64(&deps.storage, block_number).is_none());
+    let result = pow_register_ok_neuron(
+        deps.as_mut(),
+        env.clone(),
+        netuid,
+        block_number,
+        nonce,
+        work,
+        hotkey_account_id,
+        coldkey_account_id,
+    );
+    assert_eq!(result.unwrap_err(), ContractError::InvalidWorkBlock {});
+}
+
 #[test]
 fn test_registration_invalid_difficulty() {
     let (mut deps, env) = instantiate_contract();
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let netuid: u16 = 2;
     let tempo: u16 = 13;
     let hotkey_account_id = "addr1";
@@ -881,7 +1629,7 @@ fn test_registration_pruning() {
     let (mut deps, env) = instantiate_contract();
 
     let netuid: u16 = 2;
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let tempo: u16 = 13;
     let hotkey_account_id = "addr1";
     let coldkey_account_id = "addr667";
@@ -969,7 +1717,7 @@ fn test_registration_get_neuron_metadata() {
     let (mut deps, env) = instantiate_contract();
 
     let netuid: u16 = 2;
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let tempo: u16 = 13;
     let hotkey_account_id = "addr1";
     let coldkey_account_id = "addr667";
@@ -1011,7 +1759,7 @@ fn test_registration_add_network_size() {
 
     let netuid: u16 = 2;
     let netuid2: u16 = 3;
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let hotkey_account_id = "addr1";
     let hotkey_account_id1 = "addr2";
     let hotkey_account_id2 = "addr3";
@@ -1485,7 +2233,7 @@ fn test_full_pass_through() {
 fn test_registration_origin_hotkey_mismatch() {
     let (mut deps, env) = instantiate_contract();
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let netuid: u16 = 2;
     let tempo: u16 = 13;
     let hotkey_account_id_1 = "addr1";
@@ -1516,7 +2264,7 @@ fn test_registration_origin_hotkey_mismatch() {
 fn test_registration_disabled() {
     let (mut deps, env) = instantiate_contract();
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let netuid: u16 = 2;
     let tempo: u16 = 13;
     let hotkey_account_id = "addr1";
@@ -1,14 +1,20 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{coins, Addr, BankMsg, CosmosMsg};
 
 use crate::block_step::blocks_until_next_epoch;
-use crate::contract::get_economy;
+use crate::contract::{execute, get_economy};
 use crate::registration::create_work_for_block_number;
 use crate::root::{
     get_all_subnet_netuids, get_max_subnets, get_network_lock_cost, get_num_subnets,
-    get_subnet_emission_value, if_subnet_exist, remove_network, root_epoch,
-    set_lock_reduction_interval,
+    get_subnet_emission_value, if_subnet_exist, prune_root_validators_below_min_stake,
+    remove_network, root_epoch, set_lock_reduction_interval,
 };
-use crate::staking::{get_total_stake, hotkey_is_delegate};
+use crate::msg::ExecuteMsg;
+use crate::staking::{
+    decrease_stake_on_coldkey_hotkey_account, get_claimable_for_coldkey, get_total_stake,
+    hotkey_is_delegate,
+};
+use crate::state::DELEGATES;
 use crate::state_info::get_state_info;
 use crate::test_helpers::{
     add_balance_to_coldkey_account, add_network, add_stake, burned_register_ok_neuron,
@@ -16,7 +22,7 @@ use crate::test_helpers::{
     step_block,
 };
 use crate::uids::{get_subnetwork_n, get_uid_for_net_and_hotkey, is_hotkey_registered_on_network};
-use crate::utils::{do_sudo_set_block_emission, get_pending_emission, get_total_issuance, set_block_emission, set_burn, set_difficulty, set_max_allowed_uids, set_max_registrations_per_block, set_target_registrations_per_interval, set_tempo, set_weights_set_rate_limit, unstake_all};
+use crate::utils::{do_sudo_set_block_emission, get_pending_emission, get_subnet_locked_balance, get_total_issuance, set_block_emission, set_burn, set_difficulty, set_max_allowed_uids, set_max_registrations_per_block, set_target_registrations_per_interval, set_tempo, set_weights_set_rate_limit, unstake_all};
 use crate::ContractError;
 use crate::delegate_info::get_delegate_by_existing_account;
 
@@ -796,3 +802,202 @@ fn test_network_pruning() {
     step_block(deps.as_mut(), &mut env).unwrap();
     assert_eq!(get_total_issuance(&deps.storage), 585_930_498);
 }
+
+#[test]
+fn test_dissolve_network_credits_locked_balance_to_owner() {
+    let (mut deps, env) = instantiate_contract();
+
+    let owner = "addr0";
+    let lock_cost = get_network_lock_cost(&deps.storage, &deps.api, env.block.height).unwrap();
+    assert!(register_network(deps.as_mut(), env.clone(), owner).is_ok());
+    let total_issuance = get_total_issuance(&deps.storage);
+
+    let msg = ExecuteMsg::DissolveNetwork { netuid: 2 };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).is_ok());
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &Addr::unchecked(owner)), lock_cost);
+    assert_eq!(get_total_issuance(&deps.storage), total_issuance);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: coins(lock_cost as u128, "boot"),
+        })
+    );
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &Addr::unchecked(owner)), 0);
+    assert_eq!(get_total_issuance(&deps.storage), total_issuance);
+}
+
+#[test]
+fn test_remove_network_keeps_total_issuance() {
+    let (mut deps, env) = instantiate_contract();
+
+    let owner = "addr0";
+    let lock_cost = get_network_lock_cost(&deps.storage, &deps.api, env.block.height).unwrap();
+    assert!(register_network(deps.as_mut(), env.clone(), owner).is_ok());
+    let total_issuance = get_total_issuance(&deps.storage);
+
+    // The returned lock is not minted again
+    remove_network(&mut deps.storage, 2).unwrap();
+    assert!(!if_subnet_exist(&deps.storage, 2));
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &Addr::unchecked(owner)), lock_cost);
+    assert_eq!(get_total_issuance(&deps.storage), total_issuance);
+}
+
+#[test]
+fn test_deactivate_credits_only_unreserved_balance_to_root() {
+    let (mut deps, mut env) = instantiate_contract();
+    // The dmn thought name is derived from a full length contract address
+    env.contract.address =
+        Addr::unchecked("bostrom1suhgf5svhu4usrurvxzlgn54ksxmn8gljarjtxqnapv8kjnp4nrsdnqnt0");
+
+    let owner = "addr0";
+    let lock_cost = get_network_lock_cost(&deps.storage, &deps.api, env.block.height).unwrap();
+    assert!(register_network(deps.as_mut(), env.clone(), owner).is_ok());
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 1000).is_ok());
+    let reserved = get_total_stake(&deps.storage) + lock_cost;
+    let total_issuance = get_total_issuance(&deps.storage);
+
+    // The balance doesn't cover the stake and the locked balance
+    deps.querier
+        .update_balance(env.contract.address.clone(), coins(reserved as u128 - 1, "boot"));
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("root", &[]), ExecuteMsg::Deactivate {}),
+        Err(ContractError::ContractBalanceBelowReserved {})
+    );
+
+    // Only the tokens above the stake and the locked balance are returned to root
+    deps.querier
+        .update_balance(env.contract.address.clone(), coins(reserved as u128 + 500, "boot"));
+    assert!(
+        execute(deps.as_mut(), env.clone(), mock_info("root", &[]), ExecuteMsg::Deactivate {})
+            .is_ok()
+    );
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &Addr::unchecked("root")), 500);
+    assert_eq!(get_total_issuance(&deps.storage), total_issuance);
+
+    // Returned rewards are reserved for root and not swept again
+    assert!(
+        execute(deps.as_mut(), env.clone(), mock_info("root", &[]), ExecuteMsg::Deactivate {})
+            .is_ok()
+    );
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &Addr::unchecked("root")), 500);
+
+    // The locked balance goes to the subnet owner only
+    let msg = ExecuteMsg::DissolveNetwork { netuid: 2 };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).is_ok());
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &Addr::unchecked(owner)), lock_cost);
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &Addr::unchecked("root")), 500);
+
+    // Claiming the returned rewards doesn't change the total issuance
+    let total_issuance = get_total_issuance(&deps.storage);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("root", &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "root".to_string(),
+            amount: coins(500, "boot"),
+        })
+    );
+    assert_eq!(get_total_issuance(&deps.storage), total_issuance);
+}
+
+#[test]
+fn test_network_registration_refunds_overpayment() {
+    let (mut deps, env) = instantiate_contract();
+
+    let owner = "addr0";
+    let lock_cost = get_network_lock_cost(&deps.storage, &deps.api, env.block.height).unwrap();
+    let info = mock_info(owner, &coins(lock_cost as u128 + 100, "boot"));
+    let res = execute(deps.as_mut(), env.clone(), info, ExecuteMsg::RegisterNetwork {}).unwrap();
+
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: coins(100, "boot"),
+        })
+    );
+    assert_eq!(get_subnet_locked_balance(&mut deps.storage, 2), lock_cost);
+}
+
+#[test]
+fn test_root_register_min_stake_and_pruning() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 1;
+    add_network(&mut deps.storage, netuid, 13, 0);
+    set_burn(&mut deps.storage, netuid, 1);
+    for netuid in [0, netuid] {
+        set_max_registrations_per_block(&mut deps.storage, netuid, 3);
+        set_target_registrations_per_interval(&mut deps.storage, netuid, 3);
+    }
+    for hotkey in ["addr1", "addr2", "addr3"] {
+        burned_register_ok_neuron(deps.as_mut(), env.clone(), netuid, hotkey, "addr667").unwrap();
+    }
+
+    let sudo = |deps: &mut cosmwasm_std::OwnedDeps<_, _, _>, msg: ExecuteMsg| {
+        execute(deps.as_mut(), env.clone(), mock_info("root", &[]), msg).unwrap();
+    };
+    sudo(
+        &mut deps,
+        ExecuteMsg::SudoSetRootMinStake { min_stake: 100 },
+    );
+    sudo(
+        &mut deps,
+        ExecuteMsg::SudoSetDefaultTake { default_take: 1000 },
+    );
+
+    // Hotkeys below the min stake can't join root
+    let err = root_register(deps.as_mut(), env.clone(), "addr1", "addr667").unwrap_err();
+    assert_eq!(err, ContractError::StakeTooLowForRoot {});
+
+    for hotkey in ["addr1", "addr2", "addr3"] {
+        add_stake(deps.as_mut(), env.clone(), hotkey, "addr667", 150).unwrap();
+    }
+    root_register(deps.as_mut(), env.clone(), "addr1", "addr667").unwrap();
+    assert_eq!(
+        DELEGATES.load(&deps.storage, &Addr::unchecked("addr1")),
+        Ok(1000)
+    );
+
+    // Auto delegation is optional
+    sudo(
+        &mut deps,
+        ExecuteMsg::SudoSetRootAutoDelegate {
+            auto_delegate: false,
+        },
+    );
+    root_register(deps.as_mut(), env.clone(), "addr2", "addr667").unwrap();
+    assert!(!hotkey_is_delegate(
+        &deps.storage,
+        &Addr::unchecked("addr2")
+    ));
+
+    // Validators below the min stake are pruned and their slot is taken by the next registrant
+    decrease_stake_on_coldkey_hotkey_account(
+        &mut deps.storage,
+        &Addr::unchecked("addr667"),
+        &Addr::unchecked("addr1"),
+        100,
+    )
+    .unwrap();
+    assert_eq!(
+        prune_root_validators_below_min_stake(&mut deps.storage, &deps.api),
+        Ok(1)
+    );
+    assert!(!is_hotkey_registered_on_network(
+        &deps.storage,
+        0,
+        &Addr::unchecked("addr1")
+    ));
+    assert_eq!(get_subnetwork_n(&deps.storage, 0), 2);
+
+    root_register(deps.as_mut(), env.clone(), "addr3", "addr667").unwrap();
+    assert_eq!(
+        get_uid_for_net_and_hotkey(&deps.storage, 0, &Addr::unchecked("addr3")),
+        Ok(0)
+    );
+}
@@ -1,11 +1,14 @@
+use cosmwasm_std::testing::mock_info;
 use cosmwasm_std::{Addr, Uint128};
 
+use crate::contract::execute;
+use crate::msg::ExecuteMsg;
 use crate::serving::{get_axon_info, get_prometheus_info, is_valid_ip_address, is_valid_ip_type};
 use crate::test_helpers::{
     add_network, instantiate_contract, register_ok_neuron, run_step_to_block, serve_axon,
     serve_prometheus, step_block,
 };
-use crate::utils::set_serving_rate_limit;
+use crate::utils::{get_serving_allow_private_ips, set_serving_rate_limit};
 use crate::ContractError;
 
 mod test {
@@ -594,31 +597,34 @@ fn test_serving_is_valid_ip_type_nok() {
 
 #[test]
 fn test_serving_is_valid_ip_address_ipv4() {
-    assert_eq!(is_valid_ip_address(4, test::ipv4(8, 8, 8, 8)), true);
+    assert_eq!(is_valid_ip_address(4, test::ipv4(8, 8, 8, 8), false), true);
 }
 
 #[test]
 fn test_serving_is_valid_ip_address_ipv6() {
     assert_eq!(
-        is_valid_ip_address(6, test::ipv6(1, 2, 3, 4, 5, 6, 7, 8)),
+        is_valid_ip_address(6, test::ipv6(1, 2, 3, 4, 5, 6, 7, 8), false),
         true
     );
     assert_eq!(
-        is_valid_ip_address(6, test::ipv6(1, 2, 3, 4, 5, 6, 7, 8)),
+        is_valid_ip_address(6, test::ipv6(1, 2, 3, 4, 5, 6, 7, 8), false),
         true
     );
 }
 
 #[test]
 fn test_serving_is_invalid_ipv4_address() {
-    assert_eq!(is_valid_ip_address(4, test::ipv4(0, 0, 0, 0)), false);
+    assert_eq!(is_valid_ip_address(4, test::ipv4(0, 0, 0, 0), false), false);
     assert_eq!(
-        is_valid_ip_address(4, test::ipv4(255, 255, 255, 255)),
+        is_valid_ip_address(4, test::ipv4(255, 255, 255, 255), false),
         false
     );
-    assert_eq!(is_valid_ip_address(4, test::ipv4(127, 0, 0, 1)), false);
     assert_eq!(
-        is_valid_ip_address(4, test::ipv6(0xffff, 2, 3, 4, 5, 6, 7, 8)),
+        is_valid_ip_address(4, test::ipv4(127, 0, 0, 1), false),
+        false
+    );
+    assert_eq!(
+        is_valid_ip_address(4, test::ipv6(0xffff, 2, 3, 4, 5, 6, 7, 8), false),
         false
     );
 }
@@ -626,14 +632,175 @@ fn test_serving_is_invalid_ipv4_address() {
 #[test]
 fn test_serving_is_invalid_ipv6_address() {
     assert_eq!(
-        is_valid_ip_address(6, test::ipv6(0, 0, 0, 0, 0, 0, 0, 0)),
+        is_valid_ip_address(6, test::ipv6(0, 0, 0, 0, 0, 0, 0, 0), false),
         false
     );
     assert_eq!(
         is_valid_ip_address(
             4,
-            test::ipv6(0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff)
+            test::ipv6(0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff),
+            false
         ),
         false
     );
 }
+
+#[test]
+fn test_serving_is_private_or_reserved_ip_address() {
+    for ip in [
+        test::ipv4(10, 0, 0, 1),
+        test::ipv4(100, 64, 0, 1),
+        test::ipv4(127, 0, 0, 2),
+        test::ipv4(169, 254, 1, 1),
+        test::ipv4(172, 16, 0, 1),
+        test::ipv4(172, 31, 255, 254),
+        test::ipv4(192, 0, 2, 1),
+        test::ipv4(192, 168, 1, 1),
+        test::ipv4(198, 18, 0, 1),
+        test::ipv4(203, 0, 113, 1),
+        test::ipv4(224, 0, 0, 1),
+        test::ipv4(240, 0, 0, 1),
+    ] {
+        assert_eq!(is_valid_ip_address(4, ip, false), false);
+        assert_eq!(is_valid_ip_address(4, ip, true), true);
+    }
+    for ip in [
+        test::ipv6(0, 0, 0, 0, 0, 0, 0, 1),
+        test::ipv6(0, 0, 0, 0, 0, 0xffff, 0x0808, 0x0808),
+        test::ipv6(0xfe80, 0, 0, 0, 0, 0, 0, 1),
+        test::ipv6(0xfd00, 0, 0, 0, 0, 0, 0, 1),
+        test::ipv6(0xff02, 0, 0, 0, 0, 0, 0, 1),
+        test::ipv6(0x2001, 0x0db8, 0, 0, 0, 0, 0, 1),
+    ] {
+        assert_eq!(is_valid_ip_address(6, ip, false), false);
+        assert_eq!(is_valid_ip_address(6, ip, true), true);
+    }
+
+    // Neighbours of the private ranges stay public
+    assert_eq!(
+        is_valid_ip_address(4, test::ipv4(172, 32, 0, 1), false),
+        true
+    );
+    assert_eq!(
+        is_valid_ip_address(4, test::ipv4(100, 128, 0, 1), false),
+        true
+    );
+    assert_eq!(
+        is_valid_ip_address(4, test::ipv4(223, 255, 255, 1), false),
+        true
+    );
+    assert_eq!(
+        is_valid_ip_address(6, test::ipv6(0x2606, 0x4700, 0, 0, 0, 0, 0, 0x1111), false),
+        true
+    );
+
+    // The override never allows unspecified or malformed addresses
+    assert_eq!(is_valid_ip_address(4, test::ipv4(0, 0, 0, 0), true), false);
+    assert_eq!(
+        is_valid_ip_address(4, test::ipv6(0xffff, 2, 3, 4, 5, 6, 7, 8), true),
+        false
+    );
+    assert_eq!(
+        is_valid_ip_address(6, test::ipv6(0, 0, 0, 0, 0, 0, 0, 0), true),
+        false
+    );
+}
+
+#[test]
+fn test_serving_private_ips_subnet_override() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey_account_id = "addr1";
+    let netuid: u16 = 1;
+    let tempo: u16 = 13;
+    let version: u32 = 2;
+    let ip: u128 = test::ipv4(192, 168, 1, 10);
+    let port: u16 = 128;
+    let ip_type: u8 = 4;
+    let modality: u16 = 0;
+
+    add_network(&mut deps.storage, netuid, tempo, modality);
+    register_ok_neuron(
+        deps.as_mut(),
+        env.clone(),
+        netuid,
+        hotkey_account_id,
+        "addr66",
+        0,
+    );
+    set_serving_rate_limit(&mut deps.storage, netuid, 0);
+
+    let result = serve_axon(
+        deps.as_mut(),
+        env.clone(),
+        hotkey_account_id,
+        netuid,
+        version,
+        ip,
+        port,
+        ip_type,
+        0,
+        0,
+        0,
+    );
+    assert_eq!(result.unwrap_err(), ContractError::InvalidIpAddress {});
+    let result = serve_prometheus(
+        deps.as_mut(),
+        env.clone(),
+        hotkey_account_id,
+        netuid,
+        version,
+        ip,
+        port,
+        ip_type,
+    );
+    assert_eq!(result.unwrap_err(), ContractError::InvalidIpAddress {});
+
+    // Only the subnet owner or root may allow private ips
+    let msg = ExecuteMsg::SudoSetServingAllowPrivateIps {
+        netuid,
+        allow: true,
+    };
+    let result = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr66", &[]),
+        msg.clone(),
+    );
+    assert_eq!(result.unwrap_err(), ContractError::Unauthorized {});
+    assert_eq!(get_serving_allow_private_ips(&deps.storage, netuid), false);
+
+    let result = execute(deps.as_mut(), env.clone(), mock_info("root", &[]), msg);
+    assert!(result.is_ok());
+    assert_eq!(get_serving_allow_private_ips(&deps.storage, netuid), true);
+
+    let result = serve_axon(
+        deps.as_mut(),
+        env.clone(),
+        hotkey_account_id,
+        netuid,
+        version,
+        ip,
+        port,
+        ip_type,
+        0,
+        0,
+        0,
+    );
+    assert!(result.is_ok());
+    assert_eq!(
+        get_axon_info(&deps.storage, netuid, &Addr::unchecked(hotkey_account_id)).ip,
+        Uint128::from(ip)
+    );
+    let result = serve_prometheus(
+        deps.as_mut(),
+        env.clone(),
+        hotkey_account_id,
+        netuid,
+        version,
+        ip,
+        port,
+        ip_type,
+    );
+    assert!(result.is_ok());
+}
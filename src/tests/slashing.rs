@@ -0,0 +1,218 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{coins, Addr, BankMsg, CosmosMsg};
+
+use crate::block_step::blocks_until_next_epoch;
+use crate::contract::execute;
+use crate::msg::ExecuteMsg;
+use crate::staking::{
+    delegate_hotkey, get_stake_for_coldkey_and_hotkey, get_total_stake_for_hotkey,
+};
+use crate::state::{SlashingConfig, DENOM, LOW_TRUST_EPOCHS};
+use crate::test_helpers::{
+    add_network, add_stake, burned_register_ok_neuron, instantiate_contract, set_weights,
+    step_block, TestDeps,
+};
+use crate::utils::{
+    set_burn, set_max_allowed_validators, set_max_registrations_per_block, set_max_weight_limit,
+    set_min_allowed_weights, set_target_registrations_per_interval,
+};
+use crate::ContractError;
+
+const NETUID: u16 = 1;
+const TEMPO: u16 = 2;
+
+fn slashing_config(share_with_nominators: bool, treasury: Option<Addr>) -> SlashingConfig {
+    SlashingConfig {
+        trust_threshold: 100,
+        epochs: 2,
+        fraction: u16::MAX / 2,
+        share_with_nominators,
+        treasury,
+    }
+}
+
+// Registers validators addr1 and addr2 and the miner addr3 on the subnet and runs the first
+// epoch to issue validator permits. Only addr2 sets weights, so addr1 keeps its permit while
+// staying out of consensus.
+fn setup_subnet(deps: &mut TestDeps, env: &mut cosmwasm_std::Env, nominator_stake: u64) {
+    add_network(&mut deps.storage, NETUID, TEMPO, 0);
+    set_burn(&mut deps.storage, NETUID, 1);
+    set_max_registrations_per_block(&mut deps.storage, NETUID, 3);
+    set_target_registrations_per_interval(&mut deps.storage, NETUID, 3);
+    set_max_allowed_validators(&mut deps.storage, NETUID, 2);
+    set_min_allowed_weights(&mut deps.storage, NETUID, 1);
+    set_max_weight_limit(&mut deps.storage, NETUID, u16::MAX);
+
+    for (hot, cold) in [
+        ("addr1", "addr101"),
+        ("addr2", "addr102"),
+        ("addr3", "addr103"),
+    ] {
+        burned_register_ok_neuron(deps.as_mut(), env.clone(), NETUID, hot, cold).unwrap();
+    }
+    delegate_hotkey(&mut deps.storage, &Addr::unchecked("addr1"), 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr101", 1000).is_ok());
+    assert!(add_stake(
+        deps.as_mut(),
+        env.clone(),
+        "addr1",
+        "addr200",
+        nominator_stake
+    )
+    .is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr2", "addr102", 10000).is_ok());
+
+    run_to_next_epoch(deps, env);
+    set_weights(
+        deps.as_mut(),
+        env.clone(),
+        "addr2",
+        NETUID,
+        vec![2],
+        vec![u16::MAX],
+        0,
+    )
+    .unwrap();
+}
+
+// Steps blocks through the next subnet epoch and returns the bank messages of the block steps.
+fn run_to_next_epoch(
+    deps: &mut TestDeps,
+    env: &mut cosmwasm_std::Env,
+) -> Vec<CosmosMsg<cyber_std::CyberMsgWrapper>> {
+    let mut msgs = vec![];
+    loop {
+        let res = step_block(deps.as_mut(), env).unwrap();
+        msgs.extend(
+            res.messages
+                .into_iter()
+                .map(|m| m.msg)
+                .filter(|m| matches!(m, CosmosMsg::Bank(_))),
+        );
+        if blocks_until_next_epoch(NETUID, TEMPO, env.block.height) == 0 {
+            return msgs;
+        }
+    }
+}
+
+#[test]
+fn test_slash_low_trust_validator_own_stake() {
+    let (mut deps, mut env) = instantiate_contract();
+    setup_subnet(&mut deps, &mut env, 1000);
+
+    let hotkey = Addr::unchecked("addr1");
+    let owner = Addr::unchecked("addr101");
+    let nominator = Addr::unchecked("addr200");
+
+    // Only root can configure slashing, the root network runs no subnet epoch.
+    let msg = ExecuteMsg::SudoSetSlashing {
+        netuid: NETUID,
+        slashing: Some(slashing_config(false, None)),
+    };
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("addr101", &[]),
+            msg.clone()
+        )
+        .unwrap_err(),
+        ContractError::Unauthorized {}
+    );
+    let root_msg = ExecuteMsg::SudoSetSlashing {
+        netuid: 0,
+        slashing: Some(slashing_config(false, None)),
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("root", &[]), root_msg).unwrap_err(),
+        ContractError::OperationNotPermittedOnRootSubnet {}
+    );
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("root", &[]), msg).is_ok());
+
+    // The first validator stays out of consensus.
+    assert!(run_to_next_epoch(&mut deps, &mut env).is_empty());
+    assert_eq!(
+        LOW_TRUST_EPOCHS.load(&deps.storage, (NETUID, 0)).unwrap(),
+        1
+    );
+    assert!(!LOW_TRUST_EPOCHS.has(&deps.storage, (NETUID, 1)));
+
+    // Second consecutive epoch slashes only the owner stake and burns it.
+    let msgs = run_to_next_epoch(&mut deps, &mut env);
+    let denom = DENOM.load(&deps.storage).unwrap();
+    assert_eq!(
+        msgs,
+        vec![CosmosMsg::Bank(BankMsg::Burn {
+            amount: coins(499, &denom)
+        })]
+    );
+    assert_eq!(
+        get_stake_for_coldkey_and_hotkey(&deps.storage, &owner, &hotkey),
+        501
+    );
+    assert_eq!(
+        get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator, &hotkey),
+        1000
+    );
+    assert_eq!(
+        get_total_stake_for_hotkey(&deps.storage, &Addr::unchecked("addr2")),
+        10000
+    );
+    assert!(!LOW_TRUST_EPOCHS.has(&deps.storage, (NETUID, 0)));
+
+    // Recovering trust resets the counter.
+    assert!(run_to_next_epoch(&mut deps, &mut env).is_empty());
+    assert_eq!(
+        LOW_TRUST_EPOCHS.load(&deps.storage, (NETUID, 0)).unwrap(),
+        1
+    );
+    set_weights(
+        deps.as_mut(),
+        env.clone(),
+        "addr1",
+        NETUID,
+        vec![2],
+        vec![u16::MAX],
+        0,
+    )
+    .unwrap();
+    assert!(run_to_next_epoch(&mut deps, &mut env).is_empty());
+    assert!(!LOW_TRUST_EPOCHS.has(&deps.storage, (NETUID, 0)));
+}
+
+#[test]
+fn test_slash_shared_with_nominators_to_treasury() {
+    let (mut deps, mut env) = instantiate_contract();
+    setup_subnet(&mut deps, &mut env, 3000);
+
+    let hotkey = Addr::unchecked("addr1");
+    let owner = Addr::unchecked("addr101");
+    let nominator = Addr::unchecked("addr200");
+    let treasury = Addr::unchecked("treasury");
+
+    let msg = ExecuteMsg::SudoSetSlashing {
+        netuid: NETUID,
+        slashing: Some(slashing_config(true, Some(treasury.clone()))),
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("root", &[]), msg).is_ok());
+
+    assert!(run_to_next_epoch(&mut deps, &mut env).is_empty());
+    let msgs = run_to_next_epoch(&mut deps, &mut env);
+    let denom = DENOM.load(&deps.storage).unwrap();
+    assert_eq!(
+        msgs,
+        vec![CosmosMsg::Bank(BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount: coins(499 + 1499, &denom),
+        })]
+    );
+    assert_eq!(
+        get_stake_for_coldkey_and_hotkey(&deps.storage, &owner, &hotkey),
+        501
+    );
+    assert_eq!(
+        get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator, &hotkey),
+        1501
+    );
+    assert_eq!(get_total_stake_for_hotkey(&deps.storage, &hotkey), 2002);
+}
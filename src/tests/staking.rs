@@ -0,0 +1,613 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{coin, coins, Addr, BankMsg, CosmosMsg, Decimal, Order, StdResult};
+
+use crate::block_step::emit_inflation_through_hotkey_account;
+use crate::contract::execute;
+use crate::delegate_info::{get_delegated, get_delegates, get_nominators};
+use crate::msg::ExecuteMsg;
+use crate::stake_info::{get_rewards_history, get_stake_info_for_coldkey};
+use crate::staking::{
+    delegate_hotkey, expire_stake_locks, get_boosted_stake_for_hotkey, get_claimable_for_coldkey, get_stake_for_coldkey_and_hotkey, get_total_stake_for_hotkey,
+};
+use crate::state::{DelegateIdentity, DelegateLimits, RewardPreference, StakeLock, BLOCKS_PER_DAY, DELEGATE_IDENTITY, HOTKEY_REWARDS_HISTORY, MAX_LOCK_BONUS, MAX_LOCK_DURATION, TOTAL_CLAIMABLE, STAKE_LOCKS, STAKE, TOTAL_COLDKEY_STAKE, TX_RATE_LIMIT};
+use crate::test_helpers::{add_stake, instantiate_contract, root_register, sudo_register_ok_neuron};
+use crate::utils::{get_blocks_per_day, get_max_lock_bonus, get_max_lock_duration, set_max_registrations_per_block, set_target_registrations_per_interval};
+use crate::ContractError;
+
+fn delegate_identity(name: &str) -> DelegateIdentity {
+    DelegateIdentity {
+        name: name.to_string(),
+        url: "https://cyb.ai".to_string(),
+        description: "Qmd2anGbDQj7pYWMZwv9SEw11QFLQu3nzoGXfi1KwLy3Zr".to_string(),
+        image: "Qmd2anGbDQj7pYWMZwv9SEw11QFLQu3nzoGXfi1KwLy3Zr".to_string(),
+        contact: "@delegate".to_string(),
+    }
+}
+
+#[test]
+fn test_set_delegate_identity() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey_account_id = "addr1";
+    let coldkey_account_id = "addr667";
+
+    // Root registration makes the hotkey a delegate.
+    assert!(root_register(deps.as_mut(), env.clone(), hotkey_account_id, coldkey_account_id).is_ok());
+
+    // Only the owning coldkey can publish the identity.
+    let msg = ExecuteMsg::SetDelegateIdentity {
+        hotkey: hotkey_account_id.to_string(),
+        identity: delegate_identity("delegate"),
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr668", &[]), msg.clone()),
+        Err(ContractError::NonAssociatedColdKey {})
+    );
+
+    // Malformed identity is rejected.
+    let mut identity = delegate_identity("delegate");
+    identity.image = "not a particle".to_string();
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(coldkey_account_id, &[]),
+            ExecuteMsg::SetDelegateIdentity {
+                hotkey: hotkey_account_id.to_string(),
+                identity,
+            }
+        ),
+        Err(ContractError::MetadataError {})
+    );
+
+    assert!(execute(deps.as_mut(), env.clone(), mock_info(coldkey_account_id, &[]), msg).is_ok());
+    assert_eq!(
+        DELEGATE_IDENTITY
+            .load(&deps.storage, &Addr::unchecked(hotkey_account_id))
+            .unwrap(),
+        delegate_identity("delegate")
+    );
+}
+
+#[test]
+fn test_set_delegate_identity_not_delegate() {
+    let (mut deps, env) = instantiate_contract();
+
+    let netuid: u16 = 1;
+    let hotkey_account_id = "addr1";
+    let coldkey_account_id = "addr667";
+
+    sudo_register_ok_neuron(deps.as_mut(), env.clone(), netuid, hotkey_account_id, coldkey_account_id);
+
+    let msg = ExecuteMsg::SetDelegateIdentity {
+        hotkey: hotkey_account_id.to_string(),
+        identity: delegate_identity("delegate"),
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info(coldkey_account_id, &[]), msg),
+        Err(ContractError::NotDelegate {})
+    );
+}
+
+#[test]
+fn test_delegates_pagination_and_ordering() {
+    let (mut deps, env) = instantiate_contract();
+
+    set_max_registrations_per_block(&mut deps.storage, 0, 3);
+    set_target_registrations_per_interval(&mut deps.storage, 0, 3);
+    for (hot, cold) in [("addr1", "addr101"), ("addr2", "addr102"), ("addr3", "addr103")] {
+        assert!(root_register(deps.as_mut(), env.clone(), hot, cold).is_ok());
+    }
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr2", "addr200", 300).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr3", "addr200", 200).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr2", "addr201", 50).is_ok());
+
+    let delegates = |deps: &crate::test_helpers::TestDeps,
+                     start_after: Option<&str>,
+                     limit: Option<u16>,
+                     order_by_stake: Option<bool>| {
+        get_delegates(
+            deps.as_ref(),
+            start_after.map(|s| s.to_string()),
+            limit,
+            order_by_stake,
+        )
+        .unwrap()
+        .into_iter()
+        .map(|d| d.delegate.to_string())
+        .collect::<Vec<String>>()
+    };
+
+    // Ordered by address
+    assert_eq!(delegates(&deps, None, Some(2), None), vec!["addr1", "addr2"]);
+    assert_eq!(delegates(&deps, Some("addr2"), Some(2), None), vec!["addr3"]);
+
+    // Ordered by total stake
+    assert_eq!(
+        delegates(&deps, None, None, Some(true)),
+        vec!["addr2", "addr3", "addr1"]
+    );
+    assert_eq!(delegates(&deps, Some("addr2"), Some(1), Some(true)), vec!["addr3"]);
+
+    // Delegated by the nominator
+    let delegated = get_delegated(deps.as_ref(), "addr200".to_string(), None, Some(2)).unwrap();
+    assert_eq!(
+        delegated
+            .iter()
+            .map(|(d, s)| (d.delegate.to_string(), *s))
+            .collect::<Vec<(String, u64)>>(),
+        vec![("addr1".to_string(), 100), ("addr2".to_string(), 300)]
+    );
+    let delegated = get_delegated(
+        deps.as_ref(),
+        "addr200".to_string(),
+        Some("addr2".to_string()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(delegated.len(), 1);
+    assert_eq!(delegated[0].0.delegate, Addr::unchecked("addr3"));
+
+    // Nominators of the delegate, the owner without stake is skipped
+    assert_eq!(
+        get_nominators(deps.as_ref(), "addr2".to_string(), None, None).unwrap(),
+        vec![
+            (Addr::unchecked("addr200"), 300),
+            (Addr::unchecked("addr201"), 50)
+        ]
+    );
+    assert_eq!(
+        get_nominators(
+            deps.as_ref(),
+            "addr2".to_string(),
+            Some("addr200".to_string()),
+            Some(1)
+        )
+        .unwrap(),
+        vec![(Addr::unchecked("addr201"), 50)]
+    );
+
+    // Unstaking moves the delegate in the stake ordering and drops it from delegated
+    let msg = ExecuteMsg::RemoveStake {
+        hotkey: "addr2".to_string(),
+        amount: 300,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).is_ok());
+    assert_eq!(
+        delegates(&deps, None, None, Some(true)),
+        vec!["addr3", "addr1", "addr2"]
+    );
+    let delegated = get_delegated(deps.as_ref(), "addr200".to_string(), None, None).unwrap();
+    assert_eq!(
+        delegated
+            .iter()
+            .map(|(d, _)| d.delegate.to_string())
+            .collect::<Vec<String>>(),
+        vec!["addr1", "addr3"]
+    );
+}
+
+#[test]
+fn test_emission_settles_lazily_through_reward_index() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+    let owner = Addr::unchecked("addr101");
+    let nominator1 = Addr::unchecked("addr200");
+    let nominator2 = Addr::unchecked("addr201");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr201", 300).is_ok());
+
+    // Without take the emission is shared by the nominators stake.
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 1000, env.block.height).unwrap();
+    assert_eq!(get_total_stake_for_hotkey(&deps.storage, &hotkey), 1400);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &owner, &hotkey), 0);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator1, &hotkey), 350);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator2, &hotkey), 1050);
+
+    // Nominators entries are not touched by the emission.
+    assert_eq!(STAKE.load(&deps.storage, (&hotkey, &nominator2)).unwrap(), 300);
+
+    // Unstaking settles the rewards first.
+    let msg = ExecuteMsg::RemoveStake {
+        hotkey: "addr1".to_string(),
+        amount: 350,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).is_ok());
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator1, &hotkey), 0);
+    assert_eq!(get_total_stake_for_hotkey(&deps.storage, &hotkey), 1050);
+
+    // Not settled rewards don't earn until settlement.
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 900, env.block.height).unwrap();
+    assert_eq!(get_total_stake_for_hotkey(&deps.storage, &hotkey), 1950);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator2, &hotkey), 1950);
+}
+
+#[test]
+fn test_emission_without_stored_migrated_items() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+    let nominator = Addr::unchecked("addr200");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+
+    // Contracts upgraded without migration miss the items added since
+    BLOCKS_PER_DAY.remove(&mut deps.storage);
+    MAX_LOCK_DURATION.remove(&mut deps.storage);
+    MAX_LOCK_BONUS.remove(&mut deps.storage);
+    TOTAL_CLAIMABLE.remove(&mut deps.storage);
+    assert_eq!(get_blocks_per_day(&deps.storage), 14400);
+    assert_eq!(get_max_lock_duration(&deps.storage), 14400 * 365);
+    assert_eq!(get_max_lock_bonus(&deps.storage), u16::MAX);
+
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 1000, env.block.height).unwrap();
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator, &hotkey), 1100);
+
+    let msg = ExecuteMsg::LockStake {
+        hotkey: "addr1".to_string(),
+        amount: 100,
+        duration: 14400,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).is_ok());
+
+    let msg = ExecuteMsg::SetRewardPreference {
+        hotkey: "addr1".to_string(),
+        preference: RewardPreference::Payout,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).is_ok());
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 1100, env.block.height).unwrap();
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &nominator), 1100);
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), ExecuteMsg::Claim {}).is_ok());
+    assert_eq!(TOTAL_CLAIMABLE.load(&deps.storage).unwrap(), 0);
+}
+
+#[test]
+fn test_rewards_accounted_separately_from_stake() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr201", 300).is_ok());
+
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 1000, env.block.height).unwrap();
+
+    // Not settled rewards are reported
+    let stake_info = get_stake_info_for_coldkey(deps.as_ref(), "addr200".to_string()).unwrap();
+    assert_eq!(stake_info.len(), 1);
+    assert_eq!(stake_info[0].stake, 350);
+    assert_eq!(stake_info[0].rewards, 250);
+
+    // Adding principal settles rewards and doesn't count as rewards
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+    let history = get_rewards_history(
+        deps.as_ref(),
+        "addr1".to_string(),
+        "addr200".to_string(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(history.stake, 450);
+    assert_eq!(history.rewards, 250);
+    assert_eq!(
+        history.reward_per_share,
+        vec![(0, Decimal::from_ratio(5u128, 2u128))]
+    );
+
+    let history = get_rewards_history(
+        deps.as_ref(),
+        "addr1".to_string(),
+        "addr201".to_string(),
+        Some(0),
+        None,
+    )
+    .unwrap();
+    assert_eq!(history.rewards, 750);
+    assert!(history.reward_per_share.is_empty());
+}
+
+#[test]
+fn test_rewards_history_keeps_last_year() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+    let blocks_per_day = get_blocks_per_day(&deps.storage);
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+
+    for day in [0, 1, 364, 365] {
+        emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 100, day * blocks_per_day).unwrap();
+    }
+
+    // The day out of the last 365 days is dropped with the first emission of a new day
+    let days: Vec<u64> = HOTKEY_REWARDS_HISTORY
+        .prefix(&hotkey)
+        .keys(&deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()
+        .unwrap();
+    assert_eq!(days, vec![1, 364, 365]);
+}
+
+#[test]
+fn test_add_stake_for_beneficiary() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+    let payer = Addr::unchecked("addr300");
+    let beneficiary = Addr::unchecked("addr200");
+
+    sudo_register_ok_neuron(deps.as_mut(), env.clone(), 1, "addr1", "addr101");
+
+    // Only delegates or hotkeys owned by the beneficiary accept stake.
+    let msg = ExecuteMsg::AddStakeFor {
+        hotkey: "addr1".to_string(),
+        beneficiary_coldkey: "addr200".to_string(),
+    };
+    let info = mock_info("addr300", &[coin(1000, "boot")]);
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap_err(),
+        ContractError::NonAssociatedColdKey {}
+    );
+
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(execute(deps.as_mut(), env.clone(), info, msg).is_ok());
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &beneficiary, &hotkey), 1000);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &payer, &hotkey), 0);
+    assert_eq!(TOTAL_COLDKEY_STAKE.load(&deps.storage, &beneficiary).unwrap(), 1000);
+
+    // The payer can't withdraw the stake, the beneficiary can.
+    let msg = ExecuteMsg::RemoveStake {
+        hotkey: "addr1".to_string(),
+        amount: 1000,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr300", &[]), msg.clone()).is_err());
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).is_ok());
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &beneficiary, &hotkey), 0);
+}
+
+#[test]
+fn test_paid_out_rewards_are_claimable() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+    let nominator1 = Addr::unchecked("addr200");
+    let nominator2 = Addr::unchecked("addr201");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr201", 300).is_ok());
+
+    let msg = ExecuteMsg::SetRewardPreference {
+        hotkey: "addr1".to_string(),
+        preference: RewardPreference::Payout,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr201", &[]), msg).is_ok());
+
+    // Paid out rewards don't add to the stake.
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 1000, env.block.height).unwrap();
+    assert_eq!(get_total_stake_for_hotkey(&deps.storage, &hotkey), 650);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator1, &hotkey), 350);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator2, &hotkey), 300);
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &nominator2), 750);
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &nominator1), 0);
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr201", &[]), ExecuteMsg::Claim {}).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr201".to_string(),
+            amount: coins(750, "boot"),
+        })
+    );
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &nominator2), 0);
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr201", &[]), ExecuteMsg::Claim {}).unwrap_err(),
+        ContractError::NothingToClaim {}
+    );
+
+    // Switching back to compound settles the paid out rewards first.
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 400, env.block.height).unwrap();
+    let msg = ExecuteMsg::SetRewardPreference {
+        hotkey: "addr1".to_string(),
+        preference: RewardPreference::Compound,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr201", &[]), msg).is_ok());
+    assert_eq!(get_claimable_for_coldkey(&deps.storage, &nominator2), 300);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator1, &hotkey), 450);
+
+    emit_inflation_through_hotkey_account(&mut deps.storage, &deps.api, &hotkey, 0, 400, env.block.height).unwrap();
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &nominator2, &hotkey), 600);
+    assert_eq!(get_total_stake_for_hotkey(&deps.storage, &hotkey), 1150);
+}
+
+#[test]
+fn test_locked_stake_counts_with_bonus_until_unlock() {
+    let (mut deps, mut env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+    let nominator = Addr::unchecked("addr200");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 1000).is_ok());
+
+    let lock = |amount: u64, duration: u64| ExecuteMsg::LockStake {
+        hotkey: "addr1".to_string(),
+        amount,
+        duration,
+    };
+    let max_lock_duration = get_max_lock_duration(&deps.storage);
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), lock(600, max_lock_duration + 1)).unwrap_err(),
+        ContractError::InvalidLockDuration {}
+    );
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), lock(1001, 100)).unwrap_err(),
+        ContractError::NotEnoughStakeToLock {}
+    );
+
+    // Half of the max duration gets half of the max bonus.
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), lock(600, max_lock_duration / 2)).is_ok());
+    let unlock_block = env.block.height + max_lock_duration / 2;
+    assert_eq!(
+        STAKE_LOCKS.load(&deps.storage, (&hotkey, &nominator)).unwrap(),
+        StakeLock {
+            amount: 600,
+            unlock_block,
+            bonus: 300,
+        }
+    );
+    assert_eq!(get_boosted_stake_for_hotkey(&deps.storage, &hotkey), 1300);
+
+    // Locked stake can't be removed.
+    let remove = |amount: u64| ExecuteMsg::RemoveStake {
+        hotkey: "addr1".to_string(),
+        amount,
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), remove(500)).unwrap_err(),
+        ContractError::StakeLocked {}
+    );
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), remove(400)).is_ok());
+    assert_eq!(get_boosted_stake_for_hotkey(&deps.storage, &hotkey), 900);
+
+    // Expired locks are released with their bonus.
+    env.block.height = unlock_block;
+    expire_stake_locks(&mut deps.storage, env.block.height).unwrap();
+    assert!(!STAKE_LOCKS.has(&deps.storage, (&hotkey, &nominator)));
+    assert_eq!(get_boosted_stake_for_hotkey(&deps.storage, &hotkey), 600);
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), remove(600)).is_ok());
+}
+
+#[test]
+fn test_add_and_remove_stake_multi() {
+    let (mut deps, mut env) = instantiate_contract();
+    set_max_registrations_per_block(&mut deps.storage, 0, 2);
+    set_target_registrations_per_interval(&mut deps.storage, 0, 2);
+
+    let hotkey1 = Addr::unchecked("addr1");
+    let hotkey2 = Addr::unchecked("addr2");
+    let coldkey = Addr::unchecked("addr200");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    assert!(root_register(deps.as_mut(), env.clone(), "addr2", "addr102").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey1, 0);
+    delegate_hotkey(&mut deps.storage, &hotkey2, 0);
+
+    let allocations = vec![("addr1".to_string(), 100), ("addr2".to_string(), 200)];
+    let msg = ExecuteMsg::AddStakeMulti {
+        allocations: allocations.clone(),
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[coin(250, "boot")]), msg.clone()).unwrap_err(),
+        ContractError::InvalidAllocations {}
+    );
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[coin(300, "boot")]), msg).is_ok());
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &coldkey, &hotkey1), 100);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &coldkey, &hotkey2), 200);
+
+    // Any allocation above the stake fails the batch.
+    let msg = ExecuteMsg::RemoveStakeMulti {
+        allocations: vec![("addr2".to_string(), 201), ("addr1".to_string(), 50)],
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).unwrap_err(),
+        ContractError::NotEnoughStaketoWithdraw {}
+    );
+
+    let msg = ExecuteMsg::RemoveStakeMulti {
+        allocations: vec![("addr1".to_string(), 50), ("addr2".to_string(), 100)],
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr200".to_string(),
+            amount: coins(150, "boot"),
+        })
+    );
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &coldkey, &hotkey1), 50);
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &coldkey, &hotkey2), 100);
+
+    // Each batch counts as a transaction for the rate limit.
+    TX_RATE_LIMIT.save(&mut deps.storage, &1).unwrap();
+    let msg = ExecuteMsg::RemoveStakeMulti {
+        allocations: vec![("addr1".to_string(), 50)],
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg.clone()).unwrap_err(),
+        ContractError::TxRateLimitExceeded {}
+    );
+    env.block.height += 2;
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).is_ok());
+    let msg = ExecuteMsg::AddStakeMulti { allocations };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[coin(300, "boot")]), msg.clone()).unwrap_err(),
+        ContractError::TxRateLimitExceeded {}
+    );
+    env.block.height += 2;
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[coin(300, "boot")]), msg).is_ok());
+    assert_eq!(get_stake_for_coldkey_and_hotkey(&deps.storage, &coldkey, &hotkey1), 100);
+}
+
+#[test]
+fn test_delegate_limits() {
+    let (mut deps, env) = instantiate_contract();
+
+    let hotkey = Addr::unchecked("addr1");
+
+    assert!(root_register(deps.as_mut(), env.clone(), "addr1", "addr101").is_ok());
+    delegate_hotkey(&mut deps.storage, &hotkey, 0);
+
+    let set_limits = |max_delegated_stake: Option<u64>, closed: bool| ExecuteMsg::SetDelegateLimits {
+        hotkey: "addr1".to_string(),
+        limits: DelegateLimits {
+            max_delegated_stake,
+            closed,
+        },
+    };
+    assert_eq!(
+        execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), set_limits(Some(500), false)).unwrap_err(),
+        ContractError::NonAssociatedColdKey {}
+    );
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr101", &[]), set_limits(Some(500), false)).is_ok());
+
+    // Nominators stake is capped, the owner stake is not.
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 300).is_ok());
+    assert_eq!(
+        add_stake(deps.as_mut(), env.clone(), "addr1", "addr201", 300).unwrap_err(),
+        ContractError::DelegateCapacityExceeded {}
+    );
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr101", 1000).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr201", 200).is_ok());
+
+    // Closed delegate accepts stake only from existing nominators.
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr101", &[]), set_limits(None, true)).is_ok());
+    assert_eq!(
+        add_stake(deps.as_mut(), env.clone(), "addr1", "addr202", 100).unwrap_err(),
+        ContractError::DelegateClosed {}
+    );
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr200", 100).is_ok());
+    let msg = ExecuteMsg::RemoveStake {
+        hotkey: "addr1".to_string(),
+        amount: 400,
+    };
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr200", &[]), msg).is_ok());
+
+    // Removing the limits opens the delegate again.
+    assert!(execute(deps.as_mut(), env.clone(), mock_info("addr101", &[]), set_limits(None, false)).is_ok());
+    assert!(add_stake(deps.as_mut(), env.clone(), "addr1", "addr202", 100).is_ok());
+}
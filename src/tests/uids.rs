@@ -10,7 +10,7 @@ use cosmwasm_std::Addr;
 
 use crate::registration::create_work_for_block_number;
 use crate::staking::{
-    get_stake_for_coldkey_and_hotkey, get_total_stake_for_hotkey,
+    get_claimable_for_coldkey, get_stake_for_coldkey_and_hotkey, get_total_stake_for_hotkey,
     increase_stake_on_coldkey_hotkey_account,
 };
 use crate::test_helpers::{add_network, instantiate_contract, pow_register_ok_neuron};
@@ -60,7 +60,7 @@ fn test_replace_neuron() {
             .unwrap();
 
     // Replace the neuron.
-    let _msgs = replace_neuron(
+    replace_neuron(
         &mut deps.storage,
         &deps.api,
         netuid,
@@ -101,7 +101,7 @@ fn test_replace_neuron() {
 fn test_replace_neuron_multiple_subnets() {
     let (mut deps, env) = instantiate_contract();
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let netuid: u16 = 1;
     let netuid1: u16 = 2;
     let tempo: u16 = 13;
@@ -178,7 +178,7 @@ fn test_replace_neuron_multiple_subnets() {
 
     // Replace the neuron.
     // Only replace on ONE network.
-    let _msgs = replace_neuron(
+    replace_neuron(
         &mut deps.storage,
         &deps.api,
         netuid,
@@ -210,7 +210,7 @@ fn test_replace_neuron_multiple_subnets() {
 fn test_replace_neuron_multiple_subnets_unstake_all() {
     let (mut deps, env) = instantiate_contract();
 
-    let block_number: u64 = 0;
+    let block_number: u64 = env.block.height;
     let netuid: u16 = 1;
     let netuid1: u16 = 2;
     let tempo: u16 = 13;
@@ -326,7 +326,7 @@ fn test_replace_neuron_multiple_subnets_unstake_all() {
     );
 
     // Replace the neuron.
-    let _msgs = replace_neuron(
+    replace_neuron(
         &mut deps.storage,
         &deps.api,
         netuid,
@@ -375,7 +375,7 @@ fn test_replace_neuron_multiple_subnets_unstake_all() {
     );
 
     // replace on second network
-    let _msgs = replace_neuron(
+    replace_neuron(
         &mut deps.storage,
         &deps.api,
         netuid1,
@@ -400,7 +400,10 @@ fn test_replace_neuron_multiple_subnets_unstake_all() {
         ),
         0
     );
-    // assert_eq!(Balances::free_balance(&coldkey_account_id), stake_amount);
+    assert_eq!(
+        get_claimable_for_coldkey(&deps.storage, &Addr::unchecked(coldkey_account_id)),
+        stake_amount
+    );
 
     assert_eq!(
         get_stake_for_coldkey_and_hotkey(
@@ -410,10 +413,10 @@ fn test_replace_neuron_multiple_subnets_unstake_all() {
         ),
         0
     );
-    // assert_eq!(
-    //     Balances::free_balance(&coldkey_account1_id),
-    //     stake_amount + 1
-    // );
+    assert_eq!(
+        get_claimable_for_coldkey(&deps.storage, &Addr::unchecked(coldkey_account1_id)),
+        stake_amount + 1
+    );
 
     assert_eq!(
         get_stake_for_coldkey_and_hotkey(
@@ -423,10 +426,10 @@ fn test_replace_neuron_multiple_subnets_unstake_all() {
         ),
         0
     );
-    // assert_eq!(
-    //     Balances::free_balance(&coldkey_account2_id),
-    //     stake_amount + 2
-    // );
+    assert_eq!(
+        get_claimable_for_coldkey(&deps.storage, &Addr::unchecked(coldkey_account2_id)),
+        stake_amount + 2
+    );
 
     // Check total stake on neuron
     assert_eq!(
@@ -1,9 +1,9 @@
-use cosmwasm_std::{Addr, Api, CosmosMsg, Order, StdError, StdResult, Storage};
+use cosmwasm_std::{Addr, Api, Order, StdError, StdResult, Storage};
 
 use crate::staking::unstake_all_coldkeys_from_hotkey_account;
 use crate::state::{
     ACTIVE, BLOCK_AT_REGISTRATION, BONDS, CONSENSUS, DIVIDENDS, EMISSION, INCENTIVE,
-    IS_NETWORK_MEMBER, KEYS, LAST_UPDATE, PRUNING_SCORES, RANK, SUBNETWORK_N,
+    IS_NETWORK_MEMBER, KEYS, LAST_UPDATE, LOW_TRUST_EPOCHS, PRUNING_SCORES, RANK, SUBNETWORK_N,
     TRUST, UIDS, VALIDATOR_PERMIT, VALIDATOR_TRUST, WEIGHTS,
 };
 use crate::utils::set_active_for_uid;
@@ -24,7 +24,7 @@ pub fn replace_neuron(
     uid_to_replace: u16,
     new_hotkey: &Addr,
     block_number: u64,
-) -> Result<Vec<CosmosMsg>, ContractError> {
+) -> Result<(), ContractError> {
     api.debug(&format!(
         "👾 replace_neuron ( netuid: {:?} | uid_to_replace: {:?} | new_hotkey: {:?} ) ",
         netuid,
@@ -32,21 +32,9 @@ pub fn replace_neuron(
         new_hotkey.to_string()
     ));
 
-    // 1. Get the old hotkey under this position.
-    let old_hotkey: Addr = KEYS.load(store, (netuid.clone(), uid_to_replace.clone()))?;
-
-    // 2. Remove previous set memberships.
-    UIDS.remove(store, (netuid, &old_hotkey.clone()));
-    IS_NETWORK_MEMBER.remove(store, (&old_hotkey.clone(), netuid.clone()));
-    KEYS.remove(store, (netuid.clone(), uid_to_replace));
-
-    // 2a. Check if the uid is registered in any other subnetworks.
-    let mut msgs: Vec<CosmosMsg> = Vec::new();
-    let hotkey_is_registered_on_any_network: bool =
-        is_hotkey_registered_on_any_network(store, &old_hotkey);
-    if !hotkey_is_registered_on_any_network {
-        // If not, unstake all coldkeys under this hotkey.
-        msgs = unstake_all_coldkeys_from_hotkey_account(store, &old_hotkey)?;
+    // 1-2a. Remove the old hotkey under this position, the slot may be already vacant.
+    if KEYS.has(store, (netuid, uid_to_replace)) {
+        remove_neuron(store, netuid, uid_to_replace)?;
     }
 
     // 3. Create new set memberships.
@@ -67,8 +55,38 @@ pub fn replace_neuron(
         &block_number,
     )?; // Fill block at registration.
     IS_NETWORK_MEMBER.save(store, (&new_hotkey.clone(), netuid.clone()), &true)?; // Fill network is member.
+    LOW_TRUST_EPOCHS.remove(store, (netuid, uid_to_replace)); // Reset slashing counter.
+
+    Ok(())
+}
+
+// Removes the hotkey from the uid slot and leaves the slot vacant. The hotkey stake is returned
+// to the nominators claimable balances when it is not registered on any other network.
+//
+pub fn remove_neuron(store: &mut dyn Storage, netuid: u16, uid: u16) -> Result<(), ContractError> {
+    // 1. Get the old hotkey under this position.
+    let old_hotkey: Addr = KEYS.load(store, (netuid, uid))?;
+
+    // 2. Remove previous set memberships.
+    UIDS.remove(store, (netuid, &old_hotkey));
+    IS_NETWORK_MEMBER.remove(store, (&old_hotkey, netuid));
+    KEYS.remove(store, (netuid, uid));
 
-    Ok(msgs)
+    // 2a. Check if the uid is registered in any other subnetworks.
+    let hotkey_is_registered_on_any_network: bool =
+        is_hotkey_registered_on_any_network(store, &old_hotkey);
+    if !hotkey_is_registered_on_any_network {
+        // If not, unstake all coldkeys under this hotkey to their claimable balances.
+        unstake_all_coldkeys_from_hotkey_account(store, &old_hotkey)?;
+    }
+
+    Ok(())
+}
+
+// Returns the first uid slot left vacant by a deregistered neuron.
+//
+pub fn get_vacant_uid(store: &dyn Storage, netuid: u16) -> Option<u16> {
+    (0..get_subnetwork_n(store, netuid)).find(|uid| !KEYS.has(store, (netuid, *uid)))
 }
 
 // Appends the uid to the network.
@@ -1,23 +1,27 @@
 use std::ops::Deref;
 
-use cosmwasm_std::{Addr, Api, BankMsg, coins, CosmosMsg, DepsMut, ensure, Env, MessageInfo, Order, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Api, DepsMut, Empty, ensure, Env, MessageInfo, Order, StdResult, Storage};
+use cw_utils::must_pay;
 use cyber_std::Response;
 
 use crate::ContractError;
-use crate::root::if_subnet_exist;
+use crate::root::{get_root_netuid, if_subnet_exist};
 use crate::stake_info::StakeInfo;
-use crate::staking::decrease_stake_on_coldkey_hotkey_account;
+use crate::staking::{add_claimable_balance, decrease_stake_on_coldkey_hotkey_account, get_stake_for_coldkey_and_hotkey};
 use crate::state::{
     ACTIVE, ACTIVITY_CUTOFF, ADJUSTMENT_INTERVAL, ADJUSTMENTS_ALPHA, BLOCK_AT_REGISTRATION, BLOCK_EMISSION,
-    BLOCKS_SINCE_LAST_STEP, BONDS_MOVING_AVERAGE, BURN,
-    CONSENSUS, DEFAULT_TAKE, DENOM, DIFFICULTY, DIVIDENDS, EMISSION,
+    BLOCKS_PER_DAY, BLOCKS_SINCE_LAST_STEP, BONDS_MOVING_AVERAGE, BURN,
+    CONSENSUS, DEFAULT_TAKE, DIFFICULTY, DIVIDENDS, EMISSION,
     EMISSION_VALUES, IMMUNITY_PERIOD, INCENTIVE, KAPPA, LAST_TX_BLOCK, LAST_UPDATE, MAX_ALLOWED_UIDS,
     MAX_ALLOWED_VALIDATORS, MAX_BURN, MAX_DIFFICULTY, MAX_REGISTRATION_PER_BLOCK,
     MAX_WEIGHTS_LIMIT, Metadata, NETWORKS_METADATA, MIN_ALLOWED_WEIGHTS, MIN_BURN,
     MIN_DIFFICULTY, NETWORK_IMMUNITY_PERIOD, NETWORK_LOCK_REDUCTION_INTERVAL,
     NETWORK_MIN_LOCK_COST, NETWORK_RATE_LIMIT, NETWORK_REGISTRATION_ALLOWED, PRUNING_SCORES, RANK,
-    RAO_RECYCLED_FOR_REGISTRATION, REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL, RHO, ROOT,
-    SERVING_RATE_LIMIT, STAKE, SUBNET_LIMIT, SUBNET_LOCKED, SUBNET_OWNER,
+    RAO_RECYCLED_FOR_REGISTRATION, REGISTRATIONS_THIS_BLOCK, REGISTRATIONS_THIS_INTERVAL,
+    REGISTRATION_ALLOWLIST, REGISTRATION_ALLOWLIST_ENABLED, REGISTRATION_FEE_DESTINATION, REGISTRATION_MODE,
+    REGISTRATION_STAKE, RegistrationFeeDestination, RegistrationMode,
+    RHO, ROOT, ROOT_AUTO_DELEGATE, ROOT_MIN_STAKE, ALLOW_FAUCET, DENOM, FAUCET_CONFIG, FAUCET_RESERVE, FaucetConfig,
+    LOW_TRUST_EPOCHS, MAX_LOCK_BONUS, MAX_LOCK_DURATION, SERVING_ALLOW_PRIVATE_IPS, SERVING_RATE_LIMIT, SLASHING, SlashingConfig, STAKE, SUBNET_LIMIT, SUBNET_LOCKED, SUBNET_OWNER,
     SUBNET_OWNER_CUT, TARGET_REGISTRATIONS_PER_INTERVAL, TEMPO, TOTAL_ISSUANCE, TRUST, TX_RATE_LIMIT,
     VALIDATOR_PERMIT, VALIDATOR_PRUNE_LEN, VALIDATOR_TRUST, VERSE_METADATA, WEIGHTS_SET_RATE_LIMIT,
     WEIGHTS_VERSION_KEY, COMMISSION_CHANGE,
@@ -478,6 +482,52 @@ pub fn do_sudo_set_default_take(
         .add_attribute("default_take", format!("{}", default_take)))
 }
 
+pub fn get_root_min_stake(store: &dyn Storage) -> u64 {
+    ROOT_MIN_STAKE.may_load(store).unwrap().unwrap_or_default()
+}
+
+pub fn do_sudo_set_root_min_stake(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    min_stake: u64,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    ROOT_MIN_STAKE.save(deps.storage, &min_stake)?;
+    deps.api.debug(&format!(
+        "🛸 RootMinStakeSet ( min_stake: {:?} ) ",
+        min_stake
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "root_min_stake_set")
+        .add_attribute("min_stake", format!("{}", min_stake)))
+}
+
+pub fn get_root_auto_delegate(store: &dyn Storage) -> bool {
+    ROOT_AUTO_DELEGATE.may_load(store).unwrap().unwrap_or(true)
+}
+
+pub fn do_sudo_set_root_auto_delegate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    auto_delegate: bool,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    ROOT_AUTO_DELEGATE.save(deps.storage, &auto_delegate)?;
+    deps.api.debug(&format!(
+        "🛸 RootAutoDelegateSet ( auto_delegate: {:?} ) ",
+        auto_delegate
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "root_auto_delegate_set")
+        .add_attribute("auto_delegate", format!("{}", auto_delegate)))
+}
+
 // ========================
 // ========= Sudo =========
 // ========================
@@ -487,6 +537,86 @@ pub fn get_tx_rate_limit(store: &dyn Storage) -> u64 {
     TX_RATE_LIMIT.load(store).unwrap()
 }
 
+// Defaults to 5 seconds blocks for contracts migrated without the stored value.
+pub fn get_blocks_per_day(store: &dyn Storage) -> u64 {
+    BLOCKS_PER_DAY.may_load(store).unwrap().unwrap_or(14400)
+}
+
+pub fn do_sudo_set_blocks_per_day(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    blocks_per_day: u64,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    ensure!(blocks_per_day > 0, ContractError::StorageValueOutOfRange {});
+
+    BLOCKS_PER_DAY.save(deps.storage, &blocks_per_day)?;
+
+    deps.api.debug(&format!(
+        "🛸 BlocksPerDaySet ( blocks_per_day: {:?} ) ",
+        blocks_per_day
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "blocks_per_day_set")
+        .add_attribute("blocks_per_day", format!("{}", blocks_per_day)))
+}
+
+pub fn get_max_lock_duration(store: &dyn Storage) -> u64 {
+    MAX_LOCK_DURATION
+        .may_load(store)
+        .unwrap()
+        .unwrap_or(get_blocks_per_day(store) * 365)
+}
+
+pub fn do_sudo_set_max_lock_duration(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    max_lock_duration: u64,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    ensure!(max_lock_duration > 0, ContractError::StorageValueOutOfRange {});
+
+    MAX_LOCK_DURATION.save(deps.storage, &max_lock_duration)?;
+
+    deps.api.debug(&format!(
+        "🛸 MaxLockDurationSet ( max_lock_duration: {:?} ) ",
+        max_lock_duration
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "max_lock_duration_set")
+        .add_attribute("max_lock_duration", format!("{}", max_lock_duration)))
+}
+
+pub fn get_max_lock_bonus(store: &dyn Storage) -> u16 {
+    MAX_LOCK_BONUS.may_load(store).unwrap().unwrap_or(u16::MAX)
+}
+
+pub fn do_sudo_set_max_lock_bonus(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    max_lock_bonus: u16,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    MAX_LOCK_BONUS.save(deps.storage, &max_lock_bonus)?;
+
+    deps.api.debug(&format!(
+        "🛸 MaxLockBonusSet ( max_lock_bonus: {:?} ) ",
+        max_lock_bonus
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "max_lock_bonus_set")
+        .add_attribute("max_lock_bonus", format!("{}", max_lock_bonus)))
+}
+
 pub fn do_sudo_set_tx_rate_limit(
     deps: DepsMut,
     _env: Env,
@@ -516,6 +646,35 @@ pub fn set_serving_rate_limit(store: &mut dyn Storage, netuid: u16, rate_limit:
     SERVING_RATE_LIMIT.save(store, netuid, &rate_limit).unwrap()
 }
 
+pub fn get_serving_allow_private_ips(store: &dyn Storage, netuid: u16) -> bool {
+    SERVING_ALLOW_PRIVATE_IPS
+        .may_load(store, netuid)
+        .unwrap()
+        .unwrap_or(false)
+}
+
+pub fn do_sudo_set_serving_allow_private_ips(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    allow: bool,
+) -> Result<Response, ContractError> {
+    ensure_subnet_owner_or_root(deps.storage, &info.sender, netuid)?;
+
+    SERVING_ALLOW_PRIVATE_IPS.save(deps.storage, netuid, &allow)?;
+
+    deps.api.debug(&format!(
+        "🛸 ServingAllowPrivateIpsSet ( netuid: {:?} allow: {:?} ) ",
+        netuid, allow
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "serving_allow_private_ips_set")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("allow", format!("{}", allow)))
+}
+
 pub fn do_sudo_set_serving_rate_limit(
     deps: DepsMut,
     _env: Env,
@@ -999,6 +1158,92 @@ pub fn do_sudo_set_rho(
         .add_attribute("rho", format!("{}", rho)))
 }
 
+pub fn do_sudo_set_slashing(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    slashing: Option<SlashingConfig>,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    // Root network runs no subnet epoch, so its validators are never slashed.
+    ensure!(
+        netuid != get_root_netuid(),
+        ContractError::OperationNotPermittedOnRootSubnet {}
+    );
+    ensure!(
+        if_subnet_exist(deps.storage, netuid),
+        ContractError::NetworkDoesNotExist {}
+    );
+
+    match &slashing {
+        Some(config) => {
+            ensure!(
+                config.epochs > 0 && config.fraction > 0,
+                ContractError::StorageValueOutOfRange {}
+            );
+            if let Some(treasury) = &config.treasury {
+                deps.api.addr_validate(treasury.as_str())?;
+            }
+            SLASHING.save(deps.storage, netuid, config)?;
+        }
+        None => {
+            SLASHING.remove(deps.storage, netuid);
+            LOW_TRUST_EPOCHS.prefix(netuid).clear(deps.storage, None);
+        }
+    }
+
+    deps.api.debug(&format!(
+        "🛸 SlashingSet ( netuid: {:?} slashing: {:?} ) ",
+        netuid, slashing
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "slashing_set")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("enabled", format!("{}", slashing.is_some())))
+}
+
+pub fn get_registration_fee_destination(store: &dyn Storage, netuid: u16) -> RegistrationFeeDestination {
+    REGISTRATION_FEE_DESTINATION
+        .may_load(store, netuid)
+        .unwrap()
+        .unwrap_or(RegistrationFeeDestination::Burn)
+}
+
+pub fn do_sudo_set_registration_fee_destination(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    destination: RegistrationFeeDestination,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    ensure!(
+        if_subnet_exist(deps.storage, netuid),
+        ContractError::NetworkDoesNotExist {}
+    );
+    ensure!(
+        destination != RegistrationFeeDestination::SubnetOwner
+            || SUBNET_OWNER.has(deps.storage, netuid),
+        ContractError::StorageValueOutOfRange {}
+    );
+
+    REGISTRATION_FEE_DESTINATION.save(deps.storage, netuid, &destination)?;
+
+    deps.api.debug(&format!(
+        "🛸 RegistrationFeeDestinationSet ( netuid: {:?} destination: {:?} ) ",
+        netuid, destination
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "registration_fee_destination_set")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("destination", format!("{:?}", destination)))
+}
+
 pub fn get_activity_cutoff(store: &dyn Storage, netuid: u16) -> u16 {
     ACTIVITY_CUTOFF.load(store, netuid).unwrap()
 }
@@ -1048,6 +1293,222 @@ pub fn set_network_registration_allowed(
         .unwrap()
 }
 
+pub fn get_registration_mode(store: &dyn Storage, netuid: u16) -> RegistrationMode {
+    REGISTRATION_MODE
+        .may_load(store, netuid)
+        .unwrap()
+        .unwrap_or(RegistrationMode::Both)
+}
+
+pub fn do_sudo_set_registration_mode(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    mode: RegistrationMode,
+) -> Result<Response, ContractError> {
+    ensure_subnet_owner_or_root(deps.storage, &info.sender, netuid)?;
+
+    REGISTRATION_MODE.save(deps.storage, netuid, &mode)?;
+
+    deps.api.debug(&format!(
+        "🛸 RegistrationModeSet ( netuid: {:?} mode: {:?} ) ",
+        netuid, mode
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "registration_mode_set")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("mode", format!("{:?}", mode)))
+}
+
+pub fn get_faucet_config(store: &dyn Storage) -> FaucetConfig {
+    FAUCET_CONFIG.may_load(store).unwrap().unwrap_or(FaucetConfig {
+        amount: 100_000_000_000,
+        cooldown: 7200,
+        difficulty: 1_000_000,
+    })
+}
+
+pub fn get_faucet_reserve(store: &dyn Storage) -> u64 {
+    FAUCET_RESERVE.may_load(store).unwrap().unwrap_or_default()
+}
+
+pub fn do_sudo_set_allow_faucet(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    allow: bool,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    ALLOW_FAUCET.save(deps.storage, &allow)?;
+
+    deps.api
+        .debug(&format!("🛸 AllowFaucetSet ( allow: {:?} ) ", allow));
+
+    Ok(Response::default()
+        .add_attribute("action", "allow_faucet_set")
+        .add_attribute("allow", format!("{}", allow)))
+}
+
+pub fn do_sudo_set_faucet_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: FaucetConfig,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    ensure!(
+        config.amount > 0 && config.difficulty > 0,
+        ContractError::StorageValueOutOfRange {}
+    );
+
+    FAUCET_CONFIG.save(deps.storage, &config)?;
+
+    deps.api
+        .debug(&format!("🛸 FaucetConfigSet ( config: {:?} ) ", config));
+
+    Ok(Response::default()
+        .add_attribute("action", "faucet_config_set")
+        .add_attribute("amount", format!("{}", config.amount))
+        .add_attribute("cooldown", format!("{}", config.cooldown))
+        .add_attribute("difficulty", format!("{}", config.difficulty)))
+}
+
+pub fn do_sudo_fund_faucet(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_root(deps.storage, &info.sender)?;
+
+    let denom = DENOM.load(deps.storage)?;
+    let amount = must_pay(&info, &denom).map_err(|_| ContractError::CouldNotConvertToBalance {})?;
+
+    let reserve = get_faucet_reserve(deps.storage).saturating_add(amount.u128() as u64);
+    FAUCET_RESERVE.save(deps.storage, &reserve)?;
+
+    deps.api.debug(&format!(
+        "🛸 FaucetFunded ( amount: {:?} reserve: {:?} ) ",
+        amount, reserve
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "faucet_funded")
+        .add_attribute("amount", format!("{}", amount))
+        .add_attribute("reserve", format!("{}", reserve)))
+}
+
+pub fn get_registration_stake(store: &dyn Storage, netuid: u16) -> u64 {
+    REGISTRATION_STAKE
+        .may_load(store, netuid)
+        .unwrap()
+        .unwrap_or_default()
+}
+
+pub fn do_sudo_set_registration_stake(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    registration_stake: u64,
+) -> Result<Response, ContractError> {
+    ensure_subnet_owner_or_root(deps.storage, &info.sender, netuid)?;
+
+    REGISTRATION_STAKE.save(deps.storage, netuid, &registration_stake)?;
+
+    deps.api.debug(&format!(
+        "🛸 RegistrationStakeSet ( netuid: {:?} registration_stake: {:?} ) ",
+        netuid, registration_stake
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "registration_stake_set")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("registration_stake", format!("{}", registration_stake)))
+}
+
+pub fn is_registration_allowlist_enabled(store: &dyn Storage, netuid: u16) -> bool {
+    REGISTRATION_ALLOWLIST_ENABLED
+        .may_load(store, netuid)
+        .unwrap()
+        .unwrap_or(false)
+}
+
+pub fn do_sudo_set_registration_allowlist_enabled(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    ensure_subnet_owner_or_root(deps.storage, &info.sender, netuid)?;
+
+    REGISTRATION_ALLOWLIST_ENABLED.save(deps.storage, netuid, &enabled)?;
+
+    deps.api.debug(&format!(
+        "🛸 RegistrationAllowlistEnabled ( netuid: {:?} enabled: {:?} ) ",
+        netuid, enabled
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "registration_allowlist_enabled_set")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("enabled", format!("{}", enabled)))
+}
+
+pub fn do_sudo_add_to_registration_allowlist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure_subnet_owner_or_root(deps.storage, &info.sender, netuid)?;
+
+    for address in addresses.iter() {
+        let key = deps.api.addr_validate(address)?;
+        REGISTRATION_ALLOWLIST.save(deps.storage, (netuid, &key), &Empty {})?;
+    }
+
+    deps.api.debug(&format!(
+        "🛸 RegistrationAllowlistAdded ( netuid: {:?} addresses: {:?} ) ",
+        netuid, addresses
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "registration_allowlist_added")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("addresses", addresses.join(",")))
+}
+
+pub fn do_sudo_remove_from_registration_allowlist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    netuid: u16,
+    addresses: Vec<String>,
+) -> Result<Response, ContractError> {
+    ensure_subnet_owner_or_root(deps.storage, &info.sender, netuid)?;
+
+    for address in addresses.iter() {
+        let key = deps.api.addr_validate(address)?;
+        REGISTRATION_ALLOWLIST.remove(deps.storage, (netuid, &key));
+    }
+
+    deps.api.debug(&format!(
+        "🛸 RegistrationAllowlistRemoved ( netuid: {:?} addresses: {:?} ) ",
+        netuid, addresses
+    ));
+
+    Ok(Response::default()
+        .add_attribute("action", "registration_allowlist_removed")
+        .add_attribute("netuid", format!("{}", netuid))
+        .add_attribute("addresses", addresses.join(",")))
+}
+
 pub fn do_sudo_set_network_registration_allowed(
     deps: DepsMut,
     _env: Env,
@@ -1729,25 +2190,20 @@ pub fn do_sudo_unstake_all(
                 hotkey,
                 coldkey,
                 stake,
+                rewards: 0,
             })
         })
         .collect::<StdResult<Vec<_>>>()?;
 
-    let mut msgs = vec![];
+    // Unstaked tokens are credited to the claimable balances of coldkeys.
     for stake_info in stakes {
-        let (coldkey, hotkey, stake) = (stake_info.coldkey, stake_info.hotkey, stake_info.stake);
+        let (coldkey, hotkey) = (stake_info.coldkey, stake_info.hotkey);
+        let stake = get_stake_for_coldkey_and_hotkey(deps.storage, &coldkey, &hotkey);
         decrease_stake_on_coldkey_hotkey_account(deps.storage, &coldkey, &hotkey, stake)?;
-
-        let denom = DENOM.load(deps.storage)?;
-        let msg = CosmosMsg::Bank(BankMsg::Send {
-            to_address: coldkey.to_string(),
-            amount: coins(Uint128::from(stake).u128(), denom),
-        });
-        msgs.push(msg);
+        add_claimable_balance(deps.storage, &coldkey, stake);
     }
 
     Ok(Response::default()
-        .add_messages(msgs)
         .add_attribute("action", "sudo_unstake_all"))
 }
 
@@ -1774,12 +2230,14 @@ pub fn unstake_all(
                 hotkey,
                 coldkey,
                 stake,
+                rewards: 0,
             })
         })
         .collect::<StdResult<Vec<StakeInfo>>>().unwrap();
 
     for stake_info in stakes {
-        let (coldkey, hotkey, stake) = (stake_info.coldkey, stake_info.hotkey, stake_info.stake);
+        let (coldkey, hotkey) = (stake_info.coldkey, stake_info.hotkey);
+        let stake = get_stake_for_coldkey_and_hotkey(store, &coldkey, &hotkey);
         decrease_stake_on_coldkey_hotkey_account(store, &coldkey, &hotkey, stake).unwrap();
     }
 }
\ No newline at end of file